@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+
+/// Arquivo de configuração usado quando nenhum outro é indicado
+pub const DEFAULT_CONFIG_PATH: &str = "Config.toml";
+
+/// Diretório com um arquivo de configuração por perfil (emissora ou formato de debate)
+pub const PROFILES_DIR: &str = "perfis";
+
+/// Texto exibido por `--help`
+pub const USAGE: &str = "Uso: regressiva [opções]
+
+Opções:
+    --config <arquivo>        Usa o arquivo de configuração indicado (padrão: Config.toml)
+    --profile <nome>          Usa o perfil perfis/<nome>.toml
+    --print-default-config    Imprime a configuração padrão e sai
+    --check-config            Valida a configuração e sai
+    --help-config             Imprime o guia do arquivo de configuração e sai
+    --print-config-schema     Imprime o JSON Schema do arquivo de configuração e sai
+    --version                 Imprime a versão e sai
+    --help                    Imprime esta ajuda e sai";
+
+/// O que o programa deve fazer ao iniciar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Run,
+    PrintDefaultConfig,
+    CheckConfig,
+    HelpConfig,
+    PrintConfigSchema,
+    Version,
+    Help,
+}
+
+/// Argumentos da linha de comando
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliArgs {
+    pub action: Action,
+    pub config_path: PathBuf,
+    pub profile: Option<String>,
+}
+
+impl CliArgs {
+    /// Interpreta os argumentos, sem o nome do programa
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<CliArgs, String> {
+        let mut action = Action::Run;
+        let mut config_path = None;
+        let mut profile = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Aceita tanto "--config arquivo" quanto "--config=arquivo"
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => {
+                    (name.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+
+            let mut value = |name: &str| {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("A opção {} precisa de um valor", name))
+            };
+
+            match name.as_str() {
+                "--config" | "-c" => config_path = Some(PathBuf::from(value(&name)?)),
+                "--profile" | "-p" => profile = Some(value(&name)?),
+                "--print-default-config" => action = Action::PrintDefaultConfig,
+                "--check-config" => action = Action::CheckConfig,
+                "--help-config" => action = Action::HelpConfig,
+                "--print-config-schema" => action = Action::PrintConfigSchema,
+                "--version" | "-V" => action = Action::Version,
+                "--help" | "-h" => action = Action::Help,
+                _ => return Err(format!("Opção desconhecida: {}", name)),
+            }
+        }
+
+        let config_path = match (config_path, &profile) {
+            (Some(_), Some(_)) => {
+                return Err("Use --config ou --profile, não os dois".to_string());
+            }
+            (Some(path), None) => path,
+            (None, Some(profile)) => profile_path(profile),
+            (None, None) => PathBuf::from(DEFAULT_CONFIG_PATH),
+        };
+
+        Ok(CliArgs {
+            action,
+            config_path,
+            profile,
+        })
+    }
+}
+
+/// Liga a saída ao console de quem chamou pela linha de comando, que no Windows fica oculto
+#[cfg(windows)]
+pub fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // Sem console (aberto pelo Explorer), a saída continua descartada como antes
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Liga a saída ao console de quem chamou pela linha de comando, que no Windows fica oculto
+#[cfg(not(windows))]
+pub fn attach_console() {}
+
+/// Caminho do arquivo de um perfil
+pub fn profile_path(profile: &str) -> PathBuf {
+    PathBuf::from(PROFILES_DIR).join(format!("{}.toml", profile))
+}
+
+/// Lista os perfis disponíveis no diretório de perfis
+pub fn available_profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = std::fs::read_dir(PROFILES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_paths_profiles_and_actions() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.action, Action::Run);
+        assert_eq!(args.config_path, PathBuf::from("Config.toml"));
+
+        let args = parse(&["--config", "debates/municipal.toml", "--check-config"]).unwrap();
+        assert_eq!(args.action, Action::CheckConfig);
+        assert_eq!(args.config_path, PathBuf::from("debates/municipal.toml"));
+
+        let args = parse(&["--profile=presidencial"]).unwrap();
+        assert_eq!(args.profile.as_deref(), Some("presidencial"));
+        assert_eq!(args.config_path, profile_path("presidencial"));
+
+        assert_eq!(parse(&["--version"]).unwrap().action, Action::Version);
+        assert_eq!(parse(&["--help-config"]).unwrap().action, Action::HelpConfig);
+        assert_eq!(
+            parse(&["--print-default-config"]).unwrap().action,
+            Action::PrintDefaultConfig
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse(&["--config"]).is_err());
+        assert!(parse(&["--fullscreen"]).is_err());
+        assert!(parse(&["--config", "a.toml", "--profile", "estadual"]).is_err());
+    }
+}
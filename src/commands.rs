@@ -0,0 +1,140 @@
+use crate::config_loader::RegressivaConfig;
+use crate::timer::Countdown;
+use serde_derive::Deserialize;
+use std::sync::mpsc::{self, Sender};
+use std::time::{Duration, Instant};
+
+/// Tempo máximo de espera pela resposta do loop principal
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Ações que alteram o estado do timer
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    Start,
+    Pause,
+    Reset,
+    Set { seconds: u64 },
+    Preset { index: usize },
+    Nudge { seconds: i64 },
+    NextSegment,
+    Speaker { name: String },
+}
+
+impl Command {
+    /// Retorna o nome do comando usado no protocolo
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Start => "start",
+            Command::Pause => "pause",
+            Command::Reset => "reset",
+            Command::Set { .. } => "set",
+            Command::Preset { .. } => "preset",
+            Command::Nudge { .. } => "nudge",
+            Command::NextSegment => "next_segment",
+            Command::Speaker { .. } => "speaker",
+        }
+    }
+
+    /// Aplica o comando na contagem regressiva
+    pub fn apply(&self, countdown: &mut Countdown, config: &RegressivaConfig) -> Result<(), String> {
+        match self {
+            Command::Start => {
+                countdown.is_running = true;
+                countdown.start_time = Instant::now();
+            }
+            Command::Pause => {
+                countdown.is_running = false;
+            }
+            Command::Reset => {
+                countdown.is_running = false;
+                countdown.duration = Duration::new(config.tempo.get_tempo_reset(), 0);
+                countdown.resets += 1;
+            }
+            Command::Set { seconds } => {
+                countdown.is_running = false;
+                countdown.duration = Duration::new(*seconds, 0);
+            }
+            Command::Preset { index } => {
+                if !(1..=9).contains(index) {
+                    return Err(format!("preset inválido: {} (use 1 a 9)", index));
+                }
+                countdown.is_running = false;
+                countdown.duration = Duration::new(config.tempo.get_tempo(*index), 0);
+            }
+            Command::Nudge { seconds } => {
+                let delta = Duration::new(seconds.unsigned_abs(), 0);
+                countdown.duration = if *seconds >= 0 {
+                    countdown.duration.saturating_add(delta)
+                } else {
+                    countdown.duration.saturating_sub(delta)
+                };
+            }
+            Command::NextSegment => {
+                let next = countdown.segment_index.map_or(0, |index| index + 1);
+                let segmento = config
+                    .segmentos
+                    .get(next)
+                    .ok_or_else(|| "não há próximo segmento configurado".to_string())?;
+                countdown.is_running = false;
+                countdown.duration = Duration::new(segmento.get_tempo(), 0);
+                countdown.segment_index = Some(next);
+                countdown.segment = Some(segmento.get_nome().to_string());
+                if let Some(orador) = segmento.get_orador() {
+                    countdown.speaker = Some(orador.to_string());
+                }
+            }
+            Command::Speaker { name } => {
+                countdown.speaker = if name.is_empty() { None } else { Some(name.clone()) };
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pedido de um cliente remoto, com o canal para a resposta
+pub struct RemoteRequest {
+    pub command: Command,
+    pub reply: Sender<Result<(), String>>,
+}
+
+/// Envia o comando para o loop principal e aguarda a resposta
+pub fn send_command(commands: &Sender<RemoteRequest>, command: Command) -> Result<(), String> {
+    let (reply, response) = mpsc::channel();
+    commands
+        .send(RemoteRequest { command, reply })
+        .map_err(|_| "o loop principal foi encerrado".to_string())?;
+    response
+        .recv_timeout(REPLY_TIMEOUT)
+        .map_err(|_| "sem resposta do loop principal".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn huge_nudge_saturates() {
+        let config = RegressivaConfig::default();
+        let mut countdown = Countdown::new(Duration::new(60, 0));
+
+        Command::Set { seconds: u64::MAX }
+            .apply(&mut countdown, &config)
+            .unwrap();
+        Command::Nudge { seconds: 10 }
+            .apply(&mut countdown, &config)
+            .unwrap();
+        assert_eq!(countdown.duration, Duration::MAX);
+
+        countdown.duration = Duration::new(60, 0);
+        for _ in 0..2 {
+            Command::Nudge { seconds: i64::MAX }
+                .apply(&mut countdown, &config)
+                .unwrap();
+        }
+        Command::Nudge { seconds: i64::MIN }
+            .apply(&mut countdown, &config)
+            .unwrap();
+        assert_eq!(countdown.duration, Duration::MAX - Duration::new(1 << 63, 0));
+    }
+}
@@ -1,11 +1,17 @@
 use serde_derive::{Deserialize, Serialize};
+use crate::color::ConfigColor;
+use crate::config_validation;
+use crate::timer_event::TimerEvent;
+use crate::i18n;
 use sdl2::pixels::Color;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::{Path, PathBuf};
-use toml;
+use std::path::Path;
+use std::time::Duration;
 
 /// Estrutura principal de configuração
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct RegressivaConfig {
     #[serde(rename = "cores")]
    pub cores: CoresConfig,
@@ -28,92 +34,112 @@ pub struct RegressivaConfig {
     #[serde(rename = "fontes")]
     pub fontes: FontesConfig,
 
+    #[serde(rename = "renderizacao")]
+    pub renderizacao: RenderizacaoConfig,
+
     #[serde(rename = "boas_vindas")]
     pub boas_vindas: BoasVindasConfig,
-}
 
-impl Default for RegressivaConfig {
-    fn default() -> Self {
-        RegressivaConfig {
-            cores: CoresConfig::default(),
-            tempo: TempoConfig::default(),
-            tempo_texto: TempoTextoConfig::default(),
-            creditos: CreditosConfig::default(),
-            botoes: BotoesConfig::default(),
-            debug: DebugConfig::default(),
-            fontes: FontesConfig::default(),
-            boas_vindas: BoasVindasConfig::default(),
-        }
-    }
+    #[serde(rename = "idioma")]
+    pub idioma: IdiomaConfig,
+
+    #[serde(rename = "websocket")]
+    pub websocket: WebsocketConfig,
+
+    #[serde(rename = "http")]
+    pub http: HttpConfig,
+
+    #[serde(rename = "osc")]
+    pub osc: OscConfig,
+
+    #[serde(rename = "mixer")]
+    pub mixer: MixerConfig,
+
+    #[serde(rename = "arquivos_texto")]
+    pub arquivos_texto: ArquivosTextoConfig,
+
+    #[serde(rename = "sincronia")]
+    pub sincronia: SincroniaConfig,
+
+    #[serde(rename = "seguidor")]
+    pub seguidor: SeguidorConfig,
+
+    #[serde(rename = "obs")]
+    pub obs: ObsConfig,
+
+    #[serde(rename = "segmentos")]
+    pub segmentos: Vec<SegmentoConfig>,
+
+    #[serde(rename = "oradores")]
+    pub oradores: Vec<OradorConfig>,
+
+    #[serde(rename = "ganchos")]
+    pub ganchos: Vec<GanchoConfig>,
 }
 
 /// Configuração das cores
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct CoresConfig {
-    cor_background: [u8; 3],
-    cor_botoes: [u8; 3],
-    cor_vermelho: [u8; 3],
-    cor_verde: [u8; 3],
-    cor_azul: [u8; 3],
-    cor_texto: [u8; 3],
+    cor_background: ConfigColor,
+    cor_botoes: ConfigColor,
+    cor_vermelho: ConfigColor,
+    cor_verde: ConfigColor,
+    cor_azul: ConfigColor,
+    cor_texto: ConfigColor,
 }
 
+/// Transparência dos botões de iniciar, pausar e reset quando o arquivo não a define
+const ALFA_BOTOES_PADRAO: u8 = 150;
+
 impl CoresConfig {
     /// Retorna a cor de fundo
     pub fn get_background_color(&self) -> Color {
-        Color::RGB(
-            self.cor_background[0],
-            self.cor_background[1],
-            self.cor_background[2],
-        )
+        self.cor_background.to_color(255)
     }
 
     /// Retorna a cor dos botões
     pub fn get_button_color(&self) -> Color {
-        Color::RGB(self.cor_botoes[0], self.cor_botoes[1], self.cor_botoes[2])
+        self.cor_botoes.to_color(255)
     }
 
     /// Retorna a cor vermelha com transparência
     pub fn get_red_color(&self) -> Color {
-        Color::RGBA(
-            self.cor_vermelho[0],
-            self.cor_vermelho[1],
-            self.cor_vermelho[2],
-            150,
-        )
+        self.cor_vermelho.to_color(ALFA_BOTOES_PADRAO)
     }
 
     /// Retorna a cor verde com transparência
     pub fn get_green_color(&self) -> Color {
-        Color::RGBA(self.cor_verde[0], self.cor_verde[1], self.cor_verde[2], 150)
+        self.cor_verde.to_color(ALFA_BOTOES_PADRAO)
     }
 
     /// Retorna a cor azul com transparência
     pub fn get_blue_color(&self) -> Color {
-        Color::RGBA(self.cor_azul[0], self.cor_azul[1], self.cor_azul[2], 150)
+        self.cor_azul.to_color(ALFA_BOTOES_PADRAO)
     }
 
     /// Retorna a cor do texto
     pub fn get_text_color(&self) -> Color {
-        Color::RGB(self.cor_texto[0], self.cor_texto[1], self.cor_texto[2])
+        self.cor_texto.to_color(255)
     }
 }
 
 impl Default for CoresConfig {
     fn default() -> Self {
         CoresConfig {
-            cor_background: [9, 61, 83],
-            cor_botoes: [255, 255, 255],
-            cor_vermelho: [100, 0, 0],
-            cor_verde: [0, 100, 0],
-            cor_azul: [0, 0, 100],
-            cor_texto: [0, 0, 0],
+            cor_background: ConfigColor::rgb(9, 61, 83),
+            cor_botoes: ConfigColor::rgb(255, 255, 255),
+            cor_vermelho: ConfigColor::rgba(100, 0, 0, ALFA_BOTOES_PADRAO),
+            cor_verde: ConfigColor::rgba(0, 100, 0, ALFA_BOTOES_PADRAO),
+            cor_azul: ConfigColor::rgba(0, 0, 100, ALFA_BOTOES_PADRAO),
+            cor_texto: ConfigColor::rgb(0, 0, 0),
         }
     }
 }
 
 /// Configuração das boas-vindas
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct BoasVindasConfig {
     mostrar_boas_vindas: bool,
 }
@@ -133,10 +159,41 @@ impl Default for BoasVindasConfig {
     }
 }
 
+/// Configuração do idioma da interface
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IdiomaConfig {
+    idioma: String,
+    textos: BTreeMap<String, String>,
+}
+
+impl IdiomaConfig {
+    /// Retorna o código do idioma (pt-BR, en ou es)
+    pub fn get_idioma(&self) -> &str {
+        &self.idioma
+    }
+
+    /// Retorna os textos que substituem os do arquivo de idioma
+    pub fn get_textos(&self) -> &BTreeMap<String, String> {
+        &self.textos
+    }
+}
+
+impl Default for IdiomaConfig {
+    fn default() -> Self {
+        IdiomaConfig {
+            idioma: i18n::DEFAULT_LANGUAGE.to_string(),
+            textos: BTreeMap::new(),
+        }
+    }
+}
+
 /// Configuração das fontes
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct FontesConfig {
     fonte: String,
+    alternativas: Vec<String>,
     fonte_gigante: u16,
     fonte_media: u16,
     fonte_grande: u16,
@@ -149,6 +206,11 @@ impl FontesConfig {
         &self.fonte
     }
 
+    /// Retorna as fontes tentadas, em ordem, se a configurada não for encontrada
+    pub fn get_alternativas(&self) -> &[String] {
+        &self.alternativas
+    }
+
     /// Retorna o tamanho da fonte gigante
     pub fn get_fonte_gigante(&self) -> u16 {
         self.fonte_gigante
@@ -173,7 +235,8 @@ impl FontesConfig {
 impl Default for FontesConfig {
     fn default() -> Self {
         FontesConfig {
-            fonte: "GlobotipoVariable-VF.ttf".to_string(),
+            fonte: String::new(),
+            alternativas: Vec::new(),
             fonte_gigante: 64,
             fonte_media: 32,
             fonte_grande: 24,
@@ -182,8 +245,79 @@ impl Default for FontesConfig {
     }
 }
 
+/// Renderizador do SDL usado por uma janela
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum Renderizador {
+    /// Renderizador com aceleração de vídeo escolhido pelo SDL
+    Acelerado,
+    /// Renderizador por software, que funciona sem driver de vídeo
+    Software,
+    /// Driver de renderização do SDL pelo nome (opengl, direct3d11, metal...)
+    Driver(String),
+}
+
+impl From<String> for Renderizador {
+    fn from(name: String) -> Self {
+        if name.eq_ignore_ascii_case("acelerado") {
+            Renderizador::Acelerado
+        } else if name.eq_ignore_ascii_case("software") {
+            Renderizador::Software
+        } else {
+            Renderizador::Driver(name)
+        }
+    }
+}
+
+impl From<Renderizador> for String {
+    fn from(renderizador: Renderizador) -> Self {
+        match renderizador {
+            Renderizador::Acelerado => "acelerado".to_string(),
+            Renderizador::Software => "software".to_string(),
+            Renderizador::Driver(name) => name,
+        }
+    }
+}
+
+/// Configuração dos renderizadores das janelas
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RenderizacaoConfig {
+    janela_timer: Renderizador,
+    janela_controle: Renderizador,
+    driver_video: String,
+}
+
+impl RenderizacaoConfig {
+    /// Retorna o renderizador da janela do timer
+    pub fn get_janela_timer(&self) -> &Renderizador {
+        &self.janela_timer
+    }
+
+    /// Retorna o renderizador da janela de controle
+    pub fn get_janela_controle(&self) -> &Renderizador {
+        &self.janela_controle
+    }
+
+    /// Retorna o driver de vídeo do SDL (x11, wayland, windows, dummy...); vazio usa o padrão do sistema
+    pub fn get_driver_video(&self) -> &str {
+        &self.driver_video
+    }
+}
+
+impl Default for RenderizacaoConfig {
+    fn default() -> Self {
+        RenderizacaoConfig {
+            janela_timer: Renderizador::Acelerado,
+            janela_controle: Renderizador::Acelerado,
+            driver_video: String::new(),
+        }
+    }
+}
+
 /// Configuração dos tempos
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct TempoConfig {
     tempo1: u64,
     tempo2: u64,
@@ -246,6 +380,7 @@ impl Default for TempoConfig {
 
 /// Configuração dos textos dos tempos
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct TempoTextoConfig {
     tempo_texto1: String,
     tempo_texto2: String,
@@ -294,6 +429,7 @@ impl Default for TempoTextoConfig {
 
 /// Configuração dos créditos
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct CreditosConfig {
     credito_texto: String,
     mostrar_creditos: bool,
@@ -322,6 +458,7 @@ impl Default for CreditosConfig {
 
 /// Configuração dos botões
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct BotoesConfig {
     botao_reset: bool,
     botao_iniciar: bool,
@@ -355,54 +492,672 @@ impl Default for BotoesConfig {
     }
 }
 
-/// Configuração de debug
+/// Nível mínimo das mensagens gravadas no log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NivelLog {
+    Desligado,
+    Erro,
+    Aviso,
+    Info,
+    Depuracao,
+}
+
+/// Configuração de depuração e do arquivo de log
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(from = "DebugConfigArquivo")]
 pub struct DebugConfig {
-    mostrar_qps: bool,
-    mostrar_timer: bool,
+    nivel: NivelLog,
+    categorias: BTreeMap<String, NivelLog>,
+    arquivo: String,
+    tamanho_maximo_kb: u64,
+    arquivos_antigos: u32,
+    console: bool,
 }
 
 impl DebugConfig {
-    /// Retorna se deve mostrar o QPS (Quadros Por Segundo)
-    pub fn mostrar_qps(&self) -> bool {
-        self.mostrar_qps
+    /// Retorna o nível das categorias sem nível próprio
+    pub fn get_nivel(&self) -> NivelLog {
+        self.nivel
+    }
+
+    /// Retorna o nível de cada categoria (config, fonts, obs, qps, timer...)
+    pub fn get_categorias(&self) -> &BTreeMap<String, NivelLog> {
+        &self.categorias
+    }
+
+    /// Retorna o arquivo de log (vazio para não gravar)
+    pub fn get_arquivo(&self) -> &str {
+        &self.arquivo
+    }
+
+    /// Retorna o tamanho, em bytes, a partir do qual o arquivo de log é girado
+    pub fn get_tamanho_maximo(&self) -> u64 {
+        self.tamanho_maximo_kb.saturating_mul(1024)
+    }
+
+    /// Retorna quantos arquivos de log antigos são mantidos
+    pub fn get_arquivos_antigos(&self) -> u32 {
+        self.arquivos_antigos
     }
 
-    /// Retorna se deve mostrar o timer
-    pub fn mostrar_timer(&self) -> bool {
-        self.mostrar_timer
+    /// Retorna se o log também deve ser escrito no stderr
+    pub fn console(&self) -> bool {
+        self.console
+    }
+
+    /// Retorna se a categoria registra mensagens de depuração, como o QPS exibido na tela
+    pub fn depurar(&self, categoria: &str) -> bool {
+        self.categorias.get(categoria).copied().unwrap_or(self.nivel) >= NivelLog::Depuracao
     }
 }
 
 impl Default for DebugConfig {
     fn default() -> Self {
         DebugConfig {
+            nivel: NivelLog::Info,
+            categorias: BTreeMap::new(),
+            arquivo: "regressiva.log".to_string(),
+            tamanho_maximo_kb: 1024,
+            arquivos_antigos: 3,
+            console: false,
+        }
+    }
+}
+
+// Seção [debug] como escrita no arquivo, aceitando as chaves das versões anteriores
+#[derive(Deserialize)]
+#[serde(default)]
+struct DebugConfigArquivo {
+    nivel: NivelLog,
+    categorias: BTreeMap<String, NivelLog>,
+    arquivo: String,
+    tamanho_maximo_kb: u64,
+    arquivos_antigos: u32,
+    console: bool,
+    mostrar_qps: bool,
+    mostrar_timer: bool,
+}
+
+impl Default for DebugConfigArquivo {
+    fn default() -> Self {
+        let padrao = DebugConfig::default();
+        DebugConfigArquivo {
+            nivel: padrao.nivel,
+            categorias: padrao.categorias,
+            arquivo: padrao.arquivo,
+            tamanho_maximo_kb: padrao.tamanho_maximo_kb,
+            arquivos_antigos: padrao.arquivos_antigos,
+            console: padrao.console,
             mostrar_qps: false,
             mostrar_timer: false,
         }
     }
 }
 
+impl From<DebugConfigArquivo> for DebugConfig {
+    fn from(arquivo: DebugConfigArquivo) -> Self {
+        let mut categorias = arquivo.categorias;
+        // mostrar_qps e mostrar_timer viraram as categorias qps e timer em nível de depuração
+        for (categoria, ligado) in [("qps", arquivo.mostrar_qps), ("timer", arquivo.mostrar_timer)] {
+            if ligado {
+                categorias
+                    .entry(categoria.to_string())
+                    .or_insert(NivelLog::Depuracao);
+            }
+        }
+        DebugConfig {
+            nivel: arquivo.nivel,
+            categorias,
+            arquivo: arquivo.arquivo,
+            tamanho_maximo_kb: arquivo.tamanho_maximo_kb,
+            arquivos_antigos: arquivo.arquivos_antigos,
+            console: arquivo.console,
+        }
+    }
+}
+
+/// Configuração do servidor WebSocket de estado
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WebsocketConfig {
+    habilitado: bool,
+    endereco: String,
+    porta: u16,
+    intervalo_ms: u64,
+    permitir_comandos: bool,
+}
+
+impl WebsocketConfig {
+    /// Retorna se o servidor WebSocket deve ser iniciado
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o endereço de escuta no formato endereco:porta
+    pub fn get_endereco(&self) -> String {
+        format!("{}:{}", self.endereco, self.porta)
+    }
+
+    /// Retorna o intervalo entre envios periódicos do estado
+    pub fn get_intervalo(&self) -> Duration {
+        Duration::from_millis(self.intervalo_ms)
+    }
+
+    /// Retorna se os clientes podem enviar comandos de controle
+    pub fn permitir_comandos(&self) -> bool {
+        self.permitir_comandos
+    }
+}
+
+impl Default for WebsocketConfig {
+    fn default() -> Self {
+        WebsocketConfig {
+            habilitado: false,
+            endereco: "127.0.0.1".to_string(),
+            porta: 8765,
+            intervalo_ms: 250,
+            permitir_comandos: false,
+        }
+    }
+}
+
+/// Configuração da API HTTP de controle e status
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    habilitado: bool,
+    endereco: String,
+    porta: u16,
+}
+
+impl HttpConfig {
+    /// Retorna se a API HTTP deve ser iniciada
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o endereço de escuta no formato endereco:porta
+    pub fn get_endereco(&self) -> String {
+        format!("{}:{}", self.endereco, self.porta)
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            habilitado: false,
+            endereco: "127.0.0.1".to_string(),
+            porta: 8080,
+        }
+    }
+}
+
+/// Configuração da entrada e saída OSC (Open Sound Control)
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OscConfig {
+    habilitado: bool,
+    endereco: String,
+    porta: u16,
+    destinos: Vec<String>,
+}
+
+impl OscConfig {
+    /// Retorna se o OSC deve ser iniciado
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o endereço de escuta no formato endereco:porta
+    pub fn get_endereco(&self) -> String {
+        format!("{}:{}", self.endereco, self.porta)
+    }
+
+    /// Retorna os destinos (endereco:porta) que recebem o estado do timer
+    pub fn get_destinos(&self) -> &[String] {
+        &self.destinos
+    }
+}
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        OscConfig {
+            habilitado: false,
+            endereco: "127.0.0.1".to_string(),
+            porta: 9000,
+            destinos: vec!["127.0.0.1:9001".to_string()],
+        }
+    }
+}
+
+/// Configuração do corte automático de microfones na mesa de som
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MixerConfig {
+    habilitado: bool,
+    destino: String,
+    carencia_ms: u64,
+    tecla_manter_aberto: String,
+    mensagens: Vec<MensagemOscConfig>,
+}
+
+impl MixerConfig {
+    /// Retorna se o corte automático está habilitado
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o endereço (endereco:porta) da mesa de som
+    pub fn get_destino(&self) -> &str {
+        &self.destino
+    }
+
+    /// Retorna o tempo de carência entre o zero e o corte do microfone
+    pub fn get_carencia(&self) -> Duration {
+        Duration::from_millis(self.carencia_ms)
+    }
+
+    /// Retorna o nome da tecla que mantém o microfone aberto
+    pub fn get_tecla_manter_aberto(&self) -> &str {
+        &self.tecla_manter_aberto
+    }
+
+    /// Retorna as mensagens padrão de corte; `{canal}` é trocado pelo canal do orador
+    pub fn get_mensagens(&self) -> &[MensagemOscConfig] {
+        &self.mensagens
+    }
+}
+
+impl Default for MixerConfig {
+    fn default() -> Self {
+        MixerConfig {
+            habilitado: false,
+            destino: "192.168.0.100:10023".to_string(),
+            carencia_ms: 2000,
+            tecla_manter_aberto: "F2".to_string(),
+            mensagens: vec![MensagemOscConfig {
+                endereco: "/ch/{canal}/mix/on".to_string(),
+                argumentos: vec![ValorOsc::Inteiro(0)],
+            }],
+        }
+    }
+}
+
+/// Mensagem OSC configurável
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MensagemOscConfig {
+    endereco: String,
+    #[serde(default)]
+    argumentos: Vec<ValorOsc>,
+}
+
+impl MensagemOscConfig {
+    /// Retorna o endereço OSC da mensagem
+    pub fn get_endereco(&self) -> &str {
+        &self.endereco
+    }
+
+    /// Retorna os argumentos da mensagem
+    pub fn get_argumentos(&self) -> &[ValorOsc] {
+        &self.argumentos
+    }
+}
+
+/// Valor de um argumento OSC escrito no Config.toml
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ValorOsc {
+    Inteiro(i32),
+    Decimal(f32),
+    Texto(String),
+}
+
+/// Configuração de um orador e do seu canal na mesa de som
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OradorConfig {
+    nome: String,
+    canal: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mensagens: Vec<MensagemOscConfig>,
+}
+
+impl OradorConfig {
+    /// Retorna o nome do orador
+    pub fn get_nome(&self) -> &str {
+        &self.nome
+    }
+
+    /// Retorna o canal do orador na mesa de som
+    pub fn get_canal(&self) -> u32 {
+        self.canal
+    }
+
+    /// Retorna as mensagens de corte próprias do orador, se houver
+    pub fn get_mensagens(&self) -> &[MensagemOscConfig] {
+        &self.mensagens
+    }
+}
+
+/// Configuração dos arquivos de texto com o estado do timer
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ArquivosTextoConfig {
+    habilitado: bool,
+    arquivo_tempo: String,
+    arquivo_orador: String,
+    arquivo_segmento: String,
+}
+
+impl ArquivosTextoConfig {
+    /// Retorna se os arquivos de texto devem ser gravados
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o arquivo com o tempo restante (vazio para não gravar)
+    pub fn get_arquivo_tempo(&self) -> &str {
+        &self.arquivo_tempo
+    }
+
+    /// Retorna o arquivo com o nome do orador (vazio para não gravar)
+    pub fn get_arquivo_orador(&self) -> &str {
+        &self.arquivo_orador
+    }
+
+    /// Retorna o arquivo com o nome do segmento (vazio para não gravar)
+    pub fn get_arquivo_segmento(&self) -> &str {
+        &self.arquivo_segmento
+    }
+}
+
+impl Default for ArquivosTextoConfig {
+    fn default() -> Self {
+        ArquivosTextoConfig {
+            habilitado: false,
+            arquivo_tempo: "regressiva_tempo.txt".to_string(),
+            arquivo_orador: "regressiva_orador.txt".to_string(),
+            arquivo_segmento: "regressiva_segmento.txt".to_string(),
+        }
+    }
+}
+
+/// Papel da instância na sincronia entre máquinas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModoSincronia {
+    Desligado,
+    Primario,
+    Replica,
+}
+
+/// Configuração da sincronia entre instância primária e réplicas
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SincroniaConfig {
+    modo: ModoSincronia,
+    escuta: String,
+    primario: String,
+    intervalo_ms: u64,
+    tempo_limite_ms: u64,
+    promover_automaticamente: bool,
+    tecla_promover: String,
+}
+
+impl SincroniaConfig {
+    /// Retorna o papel desta instância
+    pub fn get_modo(&self) -> ModoSincronia {
+        self.modo
+    }
+
+    /// Retorna o endereço em que o primário atende as réplicas
+    pub fn get_escuta(&self) -> &str {
+        &self.escuta
+    }
+
+    /// Retorna o endereço do primário seguido pela réplica
+    pub fn get_primario(&self) -> &str {
+        &self.primario
+    }
+
+    /// Retorna o intervalo entre os sinais de vida do primário
+    pub fn get_intervalo(&self) -> Duration {
+        Duration::from_millis(self.intervalo_ms)
+    }
+
+    /// Retorna o tempo sem notícias do primário para considerar o link perdido
+    pub fn get_tempo_limite(&self) -> Duration {
+        Duration::from_millis(self.tempo_limite_ms)
+    }
+
+    /// Retorna se a réplica assume como primário quando o link cai
+    pub fn promover_automaticamente(&self) -> bool {
+        self.promover_automaticamente
+    }
+
+    /// Retorna o nome da tecla que promove a réplica a primário
+    pub fn get_tecla_promover(&self) -> &str {
+        &self.tecla_promover
+    }
+}
+
+impl Default for SincroniaConfig {
+    fn default() -> Self {
+        SincroniaConfig {
+            modo: ModoSincronia::Desligado,
+            escuta: "0.0.0.0:8766".to_string(),
+            primario: "192.168.0.10:8766".to_string(),
+            intervalo_ms: 200,
+            tempo_limite_ms: 3000,
+            promover_automaticamente: true,
+            tecla_promover: "F3".to_string(),
+        }
+    }
+}
+
+/// Configuração do modo seguidor, que apenas exibe o timer de outra instância
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SeguidorConfig {
+    habilitado: bool,
+    endereco: String,
+}
+
+impl SeguidorConfig {
+    /// Retorna se a instância deve abrir apenas o display seguindo outra instância
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o endereço (endereco:porta) do servidor WebSocket da instância seguida
+    pub fn get_endereco(&self) -> &str {
+        &self.endereco
+    }
+}
+
+impl Default for SeguidorConfig {
+    fn default() -> Self {
+        SeguidorConfig {
+            habilitado: false,
+            endereco: "192.168.0.10:8765".to_string(),
+        }
+    }
+}
+
+/// Configuração da integração com o OBS via obs-websocket 5
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ObsConfig {
+    habilitado: bool,
+    endereco: String,
+    senha: String,
+    acoes: Vec<AcaoObsConfig>,
+}
+
+impl ObsConfig {
+    /// Retorna se a integração com o OBS está habilitada
+    pub fn habilitado(&self) -> bool {
+        self.habilitado
+    }
+
+    /// Retorna o endereço (endereco:porta) do obs-websocket
+    pub fn get_endereco(&self) -> &str {
+        &self.endereco
+    }
+
+    /// Retorna a senha do obs-websocket (vazia se a autenticação estiver desligada)
+    pub fn get_senha(&self) -> &str {
+        &self.senha
+    }
+
+    /// Retorna as ações executadas nos eventos do timer
+    pub fn get_acoes(&self) -> &[AcaoObsConfig] {
+        &self.acoes
+    }
+}
+
+impl Default for ObsConfig {
+    fn default() -> Self {
+        ObsConfig {
+            habilitado: false,
+            endereco: "127.0.0.1:4455".to_string(),
+            senha: String::new(),
+            acoes: Vec::new(),
+        }
+    }
+}
+
+/// Requisição do obs-websocket executada quando um evento do timer ocorre
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AcaoObsConfig {
+    evento: TimerEvent,
+    tipo: String,
+    #[serde(default)]
+    dados: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AcaoObsConfig {
+    /// Retorna o evento que dispara a ação
+    pub fn get_evento(&self) -> TimerEvent {
+        self.evento
+    }
+
+    /// Retorna o tipo da requisição (ex.: SetCurrentProgramScene)
+    pub fn get_tipo(&self) -> &str {
+        &self.tipo
+    }
+
+    /// Retorna os dados da requisição
+    pub fn get_dados(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.dados
+    }
+}
+
+/// Configuração de um segmento do debate
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SegmentoConfig {
+    nome: String,
+    tempo: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    orador: Option<String>,
+}
+
+impl SegmentoConfig {
+    /// Retorna o nome do segmento
+    pub fn get_nome(&self) -> &str {
+        &self.nome
+    }
+
+    /// Retorna a duração do segmento em segundos
+    pub fn get_tempo(&self) -> u64 {
+        self.tempo
+    }
+
+    /// Retorna o orador do segmento, se houver
+    pub fn get_orador(&self) -> Option<&str> {
+        self.orador.as_deref()
+    }
+}
+
+/// Tempo limite padrão para a execução de um gancho
+pub const TEMPO_LIMITE_GANCHO_MS: u64 = 5000;
+
+/// Comando externo executado quando um evento do timer ocorre
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GanchoConfig {
+    evento: TimerEvent,
+    comando: String,
+    #[serde(default)]
+    argumentos: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tempo_limite_ms: Option<u64>,
+}
+
+impl GanchoConfig {
+    /// Retorna o evento que dispara o gancho
+    pub fn get_evento(&self) -> TimerEvent {
+        self.evento
+    }
+
+    /// Retorna o programa executado
+    pub fn get_comando(&self) -> &str {
+        &self.comando
+    }
+
+    /// Retorna os argumentos passados ao programa
+    pub fn get_argumentos(&self) -> &[String] {
+        &self.argumentos
+    }
+
+    /// Retorna o tempo máximo de execução antes de o processo ser encerrado
+    pub fn get_tempo_limite(&self) -> Duration {
+        Duration::from_millis(self.tempo_limite_ms.unwrap_or(TEMPO_LIMITE_GANCHO_MS))
+    }
+}
+
 impl RegressivaConfig {
     /// Carrega a configuração do arquivo TOML ou cria um novo com valores padrão
-    pub fn load_config() -> Result<RegressivaConfig, Box<dyn std::error::Error>> {
-        let config_path = PathBuf::from("Config.toml");
-
+    pub fn load_config(config_path: &Path) -> Result<RegressivaConfig, Box<dyn std::error::Error>> {
         if !config_path.exists() {
             let default_config = RegressivaConfig::default();
-            default_config.save_config(&config_path)?;
-            println!("Created default configuration file: {:?}", config_path);
+            default_config.save_config(config_path)?;
+            log::info!(
+                target: "config",
+                "Arquivo de configuração padrão criado: {:?}",
+                config_path
+            );
             return Ok(default_config);
         }
 
-        let config_str = fs::read_to_string(&config_path)?;
-        let config: RegressivaConfig = toml::from_str(&config_str)?;
-        Ok(config)
+        RegressivaConfig::read_config(config_path)
+    }
+
+    /// Lê e valida a configuração de um arquivo TOML existente, sem criá-lo
+    pub fn read_config(config_path: &Path) -> Result<RegressivaConfig, Box<dyn std::error::Error>> {
+        let config_str = fs::read_to_string(config_path)?;
+        Ok(config_validation::parse(&config_str, config_path)?)
+    }
+
+    /// Aplica as seções que podem mudar com o programa em execução: aparência, tempos, botões e idioma
+    pub fn apply_live(&mut self, new_config: RegressivaConfig) {
+        self.cores = new_config.cores;
+        self.tempo = new_config.tempo;
+        self.tempo_texto = new_config.tempo_texto;
+        self.creditos = new_config.creditos;
+        self.botoes = new_config.botoes;
+        self.fontes = new_config.fontes;
+        self.debug = new_config.debug;
+        self.idioma = new_config.idioma;
+    }
+
+    /// Gera o conteúdo TOML da configuração
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
     }
 
-    fn save_config(&self, config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-        let toml_string = toml::to_string_pretty(self)?;
-        fs::write(config_path, toml_string)?;
+    /// Grava a configuração no arquivo TOML
+    pub fn save_config(&self, config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(config_path, self.to_toml_string()?)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
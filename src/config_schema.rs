@@ -0,0 +1,690 @@
+use crate::config_loader::{
+    ModoSincronia, NivelLog, RegressivaConfig, Renderizador, TEMPO_LIMITE_GANCHO_MS,
+};
+use crate::timer_event::TimerEvent;
+use crate::i18n::{self, Texts};
+use serde_json::{json, Map, Value as Json};
+use toml::Value;
+use FieldType::*;
+
+/// Tipo de valor aceito por um campo do Config.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    Integer,
+    Port,
+    FontSize,
+    Boolean,
+    Text,
+    TextList,
+    Color,
+    Language,
+    TextMap,
+    SyncMode,
+    LogLevel,
+    LogLevels,
+    Renderer,
+    Event,
+    OscMessages,
+    Table,
+}
+
+/// Campo de uma seção; a descrição fica nos arquivos de idioma em guia.<seção>.<campo>
+#[derive(Debug, Clone, Copy)]
+pub struct FieldDoc {
+    pub name: &'static str,
+    pub field_type: FieldType,
+    /// Campo repetido de 1 a 9, como tempo1 ... tempo9
+    pub numbered: bool,
+    /// Obrigatório nos itens das listas de tabelas, como [[segmentos]]
+    pub required: bool,
+    /// Valor padrão dos campos de listas de tabelas, que não aparecem na configuração padrão
+    pub default: Option<u64>,
+}
+
+/// Seção do Config.toml
+#[derive(Debug, Clone, Copy)]
+pub struct SectionDoc {
+    pub name: &'static str,
+    /// Lista de tabelas ([[segmentos]]) em vez de tabela única ([tempo])
+    pub array: bool,
+    pub fields: &'static [FieldDoc],
+}
+
+impl FieldDoc {
+    /// Chaves do campo no arquivo
+    pub fn keys(&self) -> Vec<String> {
+        if self.numbered {
+            (1..=9).map(|i| format!("{}{}", self.name, i)).collect()
+        } else {
+            vec![self.name.to_string()]
+        }
+    }
+}
+
+const fn field(name: &'static str, field_type: FieldType) -> FieldDoc {
+    FieldDoc {
+        name,
+        field_type,
+        numbered: false,
+        required: false,
+        default: None,
+    }
+}
+
+const fn numbered(name: &'static str, field_type: FieldType) -> FieldDoc {
+    FieldDoc {
+        numbered: true,
+        ..field(name, field_type)
+    }
+}
+
+const fn required(name: &'static str, field_type: FieldType) -> FieldDoc {
+    FieldDoc {
+        required: true,
+        ..field(name, field_type)
+    }
+}
+
+/// Chaves antigas ainda aceitas na leitura, declaradas no JSON Schema como obsoletas
+const DEPRECATED_FIELDS: [(&str, &str); 2] = [("debug", "mostrar_qps"), ("debug", "mostrar_timer")];
+
+/// Seções e campos do Config.toml, na ordem do guia
+pub const SECTIONS: &[SectionDoc] = &[
+    SectionDoc {
+        name: "tempo",
+        array: false,
+        fields: &[
+            numbered("tempo", Integer),
+            field("tempo_alerta_regressiva", Integer),
+            field("tempo_reset", Integer),
+        ],
+    },
+    SectionDoc {
+        name: "tempo_texto",
+        array: false,
+        fields: &[numbered("tempo_texto", Text)],
+    },
+    SectionDoc {
+        name: "cores",
+        array: false,
+        fields: &[
+            field("cor_background", Color),
+            field("cor_botoes", Color),
+            field("cor_vermelho", Color),
+            field("cor_verde", Color),
+            field("cor_azul", Color),
+            field("cor_texto", Color),
+        ],
+    },
+    SectionDoc {
+        name: "creditos",
+        array: false,
+        fields: &[
+            field("credito_texto", Text),
+            field("mostrar_creditos", Boolean),
+        ],
+    },
+    SectionDoc {
+        name: "botoes",
+        array: false,
+        fields: &[
+            field("botao_iniciar", Boolean),
+            field("botao_pausar", Boolean),
+            field("botao_reset", Boolean),
+        ],
+    },
+    SectionDoc {
+        name: "debug",
+        array: false,
+        fields: &[
+            field("nivel", LogLevel),
+            field("categorias", LogLevels),
+            field("arquivo", Text),
+            field("tamanho_maximo_kb", Integer),
+            field("arquivos_antigos", Integer),
+            field("console", Boolean),
+        ],
+    },
+    SectionDoc {
+        name: "fontes",
+        array: false,
+        fields: &[
+            field("fonte", Text),
+            field("alternativas", TextList),
+            field("fonte_gigante", FontSize),
+            field("fonte_grande", FontSize),
+            field("fonte_media", FontSize),
+            field("fonte_pequena", FontSize),
+        ],
+    },
+    SectionDoc {
+        name: "renderizacao",
+        array: false,
+        fields: &[
+            field("janela_timer", Renderer),
+            field("janela_controle", Renderer),
+            field("driver_video", Text),
+        ],
+    },
+    SectionDoc {
+        name: "boas_vindas",
+        array: false,
+        fields: &[field("mostrar_boas_vindas", Boolean)],
+    },
+    SectionDoc {
+        name: "idioma",
+        array: false,
+        fields: &[field("idioma", Language), field("textos", TextMap)],
+    },
+    SectionDoc {
+        name: "websocket",
+        array: false,
+        fields: &[
+            field("habilitado", Boolean),
+            field("endereco", Text),
+            field("porta", Port),
+            field("intervalo_ms", Integer),
+            field("permitir_comandos", Boolean),
+        ],
+    },
+    SectionDoc {
+        name: "segmentos",
+        array: true,
+        fields: &[
+            required("nome", Text),
+            required("tempo", Integer),
+            field("orador", Text),
+        ],
+    },
+    SectionDoc {
+        name: "http",
+        array: false,
+        fields: &[
+            field("habilitado", Boolean),
+            field("endereco", Text),
+            field("porta", Port),
+        ],
+    },
+    SectionDoc {
+        name: "osc",
+        array: false,
+        fields: &[
+            field("habilitado", Boolean),
+            field("endereco", Text),
+            field("porta", Port),
+            field("destinos", TextList),
+        ],
+    },
+    SectionDoc {
+        name: "mixer",
+        array: false,
+        fields: &[
+            field("habilitado", Boolean),
+            field("destino", Text),
+            field("carencia_ms", Integer),
+            field("tecla_manter_aberto", Text),
+            field("mensagens", OscMessages),
+        ],
+    },
+    SectionDoc {
+        name: "oradores",
+        array: true,
+        fields: &[
+            required("nome", Text),
+            required("canal", Integer),
+            field("mensagens", OscMessages),
+        ],
+    },
+    SectionDoc {
+        name: "arquivos_texto",
+        array: false,
+        fields: &[
+            field("habilitado", Boolean),
+            field("arquivo_tempo", Text),
+            field("arquivo_orador", Text),
+            field("arquivo_segmento", Text),
+        ],
+    },
+    SectionDoc {
+        name: "sincronia",
+        array: false,
+        fields: &[
+            field("modo", SyncMode),
+            field("escuta", Text),
+            field("primario", Text),
+            field("intervalo_ms", Integer),
+            field("tempo_limite_ms", Integer),
+            field("promover_automaticamente", Boolean),
+            field("tecla_promover", Text),
+        ],
+    },
+    SectionDoc {
+        name: "seguidor",
+        array: false,
+        fields: &[field("habilitado", Boolean), field("endereco", Text)],
+    },
+    SectionDoc {
+        name: "obs",
+        array: false,
+        fields: &[
+            field("habilitado", Boolean),
+            field("endereco", Text),
+            field("senha", Text),
+        ],
+    },
+    SectionDoc {
+        name: "obs.acoes",
+        array: true,
+        fields: &[
+            required("evento", Event),
+            required("tipo", Text),
+            field("dados", Table),
+        ],
+    },
+    SectionDoc {
+        name: "ganchos",
+        array: true,
+        fields: &[
+            required("evento", Event),
+            required("comando", Text),
+            field("argumentos", TextList),
+            FieldDoc {
+                default: Some(TEMPO_LIMITE_GANCHO_MS),
+                ..field("tempo_limite_ms", Integer)
+            },
+        ],
+    },
+];
+
+/// Guia do Config.toml no idioma dos textos, gerado a partir de `SECTIONS` e da configuração padrão
+pub fn guide(texts: &Texts) -> String {
+    let defaults = default_config();
+    let mut guide = texts.get("boas_vindas").to_string();
+    guide.push('\n');
+
+    for section in SECTIONS {
+        let header = if section.array {
+            format!("[[{}]]", section.name)
+        } else {
+            format!("[{}]", section.name)
+        };
+        guide.push_str(&format!(
+            "\n{} # {}\n",
+            header,
+            texts.get(&doc_key(section.name, "descricao"))
+        ));
+
+        for field in section.fields {
+            let keys = field.keys();
+            let name = match keys.as_slice() {
+                [single] => single.clone(),
+                [first, .., last] => format!("{} ... {}", first, last),
+                [] => continue,
+            };
+            let mut details = type_name(field.field_type, texts);
+            if field.required {
+                details.push_str(&format!(", {}", texts.get("guia.obrigatorio")));
+            }
+            let values: Vec<String> = keys
+                .iter()
+                .filter_map(|key| default_value(&defaults, section, field, key))
+                .map(|value| value.to_string())
+                .collect();
+            if !values.is_empty() {
+                details.push_str(&format!(
+                    ", {}: {}",
+                    texts.get("guia.padrao"),
+                    values.join(", ")
+                ));
+            }
+            guide.push_str(&format!(
+                "- {} ({}): {}\n",
+                name,
+                details,
+                texts.get(&doc_key(section.name, field.name))
+            ));
+        }
+
+        if let Some(note) = texts.lookup(&doc_key(section.name, "nota")) {
+            for line in note.lines() {
+                guide.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+
+    guide.push('\n');
+    guide.push_str(texts.get("boas_vindas_fim"));
+    guide
+}
+
+/// JSON Schema do Config.toml, para editores validarem o arquivo
+pub fn json_schema(texts: &Texts) -> Json {
+    let defaults = default_config();
+    let mut properties = Map::new();
+
+    for section in SECTIONS {
+        let mut fields = Map::new();
+        let mut required = Vec::new();
+        for field in section.fields {
+            for key in field.keys() {
+                let mut schema = field_schema(field.field_type);
+                schema.insert(
+                    "description".to_string(),
+                    json!(texts.get(&doc_key(section.name, field.name))),
+                );
+                if let Some(value) = default_value(&defaults, section, field, &key) {
+                    schema.insert("default".to_string(), json!(value));
+                }
+                if field.required {
+                    required.push(key.clone());
+                }
+                fields.insert(key, Json::Object(schema));
+            }
+        }
+        for (_, key) in DEPRECATED_FIELDS
+            .iter()
+            .filter(|(name, _)| *name == section.name)
+        {
+            let schema = json!({
+                "type": "boolean",
+                "deprecated": true,
+                "description": texts.get(&doc_key(section.name, key)),
+            });
+            fields.insert(key.to_string(), schema);
+        }
+
+        let table = json!({
+            "type": "object",
+            "properties": fields,
+            "required": required,
+            "additionalProperties": false,
+        });
+        let description_key = doc_key(section.name, "descricao");
+        let description = texts.get(&description_key);
+        let schema = if section.array {
+            json!({ "type": "array", "description": description, "items": table })
+        } else {
+            let mut table = table;
+            table["description"] = json!(description);
+            table
+        };
+
+        // Listas dentro de seções, como [[obs.acoes]], ficam nas propriedades da seção
+        match section.name.split_once('.') {
+            Some((parent, child)) => {
+                if let Some(parent) = properties.get_mut(parent) {
+                    parent["properties"][child] = schema;
+                }
+            }
+            None => {
+                properties.insert(section.name.to_string(), schema);
+            }
+        }
+    }
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Config.toml do regressiva",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+// Chave da descrição nos arquivos de idioma
+fn doc_key(section: &str, name: &str) -> String {
+    format!("guia.{}.{}", section, name)
+}
+
+fn default_config() -> Value {
+    Value::try_from(RegressivaConfig::default()).unwrap_or(Value::Table(Default::default()))
+}
+
+// Valor padrão de uma chave, tirado da configuração padrão para não divergir do código
+fn default_value(
+    defaults: &Value,
+    section: &SectionDoc,
+    field: &FieldDoc,
+    key: &str,
+) -> Option<Value> {
+    if section.array {
+        return field.default.map(|value| Value::Integer(value as i64));
+    }
+    defaults.get(section.name)?.get(key).cloned()
+}
+
+// Nome do tipo exibido no guia
+fn type_name(field_type: FieldType, texts: &Texts) -> String {
+    let choices = |choices: Vec<String>| {
+        choices
+            .iter()
+            .map(|choice| format!("{:?}", choice))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+    match field_type {
+        Integer | Port | FontSize => texts.get("tipo.inteiro").to_string(),
+        Boolean => "true/false".to_string(),
+        Text => texts.get("tipo.texto").to_string(),
+        TextList => texts.get("tipo.lista_textos").to_string(),
+        Color => texts.get("tipo.cor").to_string(),
+        TextMap => texts.get("tipo.tabela_textos").to_string(),
+        OscMessages => texts.get("tipo.mensagens_osc").to_string(),
+        Table => texts.get("tipo.tabela").to_string(),
+        LogLevels => texts.get("tipo.tabela_niveis").to_string(),
+        Language => choices(languages()),
+        SyncMode => choices(sync_modes()),
+        LogLevel => choices(log_levels()),
+        Renderer => format!(
+            "{} | {}",
+            choices(renderers()),
+            texts.get("tipo.driver_renderizacao")
+        ),
+        Event => choices(events()),
+    }
+}
+
+// Esquema JSON de um tipo de campo
+fn field_schema(field_type: FieldType) -> Map<String, Json> {
+    let schema = match field_type {
+        Integer => json!({ "type": "integer", "minimum": 0 }),
+        Port => json!({ "type": "integer", "minimum": 0, "maximum": 65535 }),
+        FontSize => json!({ "type": "integer", "minimum": 1, "maximum": 65535 }),
+        Boolean => json!({ "type": "boolean" }),
+        Text => json!({ "type": "string" }),
+        TextList => json!({ "type": "array", "items": { "type": "string" } }),
+        Color => json!({
+            "anyOf": [
+                { "type": "string" },
+                {
+                    "type": "array",
+                    "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "minItems": 3,
+                    "maxItems": 4
+                }
+            ]
+        }),
+        Language => json!({ "enum": languages() }),
+        TextMap => json!({
+            "type": "object",
+            "propertyNames": { "enum": i18n::keys() },
+            "additionalProperties": { "type": "string" }
+        }),
+        SyncMode => json!({ "enum": sync_modes() }),
+        LogLevel => json!({ "enum": log_levels() }),
+        LogLevels => json!({
+            "type": "object",
+            "additionalProperties": { "enum": log_levels() }
+        }),
+        Renderer => json!({
+            "anyOf": [{ "enum": renderers() }, { "type": "string" }]
+        }),
+        Event => json!({ "enum": events() }),
+        OscMessages => json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "endereco": { "type": "string" },
+                    "argumentos": {
+                        "type": "array",
+                        "items": { "type": ["integer", "number", "string"] }
+                    }
+                },
+                "required": ["endereco"],
+                "additionalProperties": false
+            }
+        }),
+        Table => json!({ "type": "object" }),
+    };
+    match schema {
+        Json::Object(schema) => schema,
+        _ => Map::new(),
+    }
+}
+
+fn languages() -> Vec<String> {
+    i18n::LANGUAGES
+        .iter()
+        .map(|(code, _)| code.to_string())
+        .collect()
+}
+
+fn sync_modes() -> Vec<String> {
+    [
+        ModoSincronia::Desligado,
+        ModoSincronia::Primario,
+        ModoSincronia::Replica,
+    ]
+    .into_iter()
+    .filter_map(|mode| Value::try_from(mode).ok())
+    .filter_map(|mode| mode.as_str().map(str::to_string))
+    .collect()
+}
+
+fn log_levels() -> Vec<String> {
+    [
+        NivelLog::Desligado,
+        NivelLog::Erro,
+        NivelLog::Aviso,
+        NivelLog::Info,
+        NivelLog::Depuracao,
+    ]
+    .into_iter()
+    .filter_map(|nivel| Value::try_from(nivel).ok())
+    .filter_map(|nivel| nivel.as_str().map(str::to_string))
+    .collect()
+}
+
+fn renderers() -> Vec<String> {
+    [Renderizador::Acelerado, Renderizador::Software]
+        .into_iter()
+        .filter_map(|renderer| Value::try_from(renderer).ok())
+        .filter_map(|renderer| renderer.as_str().map(str::to_string))
+        .collect()
+}
+
+fn events() -> Vec<String> {
+    TimerEvent::ALL
+        .iter()
+        .map(|event| event.label().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_cover_every_field_of_the_default_config() {
+        let defaults = default_config();
+        let defaults = defaults.as_table().unwrap();
+        for (name, value) in defaults {
+            let Some(table) = value.as_table() else {
+                // Listas de tabelas vazias na configuração padrão
+                assert!(
+                    SECTIONS.iter().any(|s| s.name == name && s.array),
+                    "{}",
+                    name
+                );
+                continue;
+            };
+            let section = SECTIONS
+                .iter()
+                .find(|s| s.name == name)
+                .unwrap_or_else(|| panic!("seção {} sem documentação", name));
+            let mut documented: Vec<String> =
+                section.fields.iter().flat_map(FieldDoc::keys).collect();
+            documented.extend(
+                SECTIONS
+                    .iter()
+                    .filter_map(|s| s.name.strip_prefix(&format!("{}.", name)))
+                    .map(str::to_string),
+            );
+            let mut keys: Vec<String> = table.keys().cloned().collect();
+            documented.sort();
+            keys.sort();
+            assert_eq!(documented, keys, "campos de [{}]", name);
+        }
+    }
+
+    #[test]
+    fn every_field_has_a_description() {
+        for section in SECTIONS {
+            assert!(i18n::is_known_key(&doc_key(section.name, "descricao")));
+            for field in section.fields {
+                assert!(
+                    i18n::is_known_key(&doc_key(section.name, field.name)),
+                    "{}.{} sem descrição",
+                    section.name,
+                    field.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn guide_lists_types_and_defaults() {
+        let guide = guide(&Texts::new(&Default::default()));
+        assert!(guide.starts_with("Bem-vindo ao programa regressiva!"));
+        assert!(guide.contains("\n[tempo] # Tempos dos botões, em segundos\n"));
+        assert!(guide.contains(
+            "- tempo1 ... tempo9 (inteiro, padrão: 0, 15, 30, 45, 60, 75, 90, 105, 120): "
+        ));
+        assert!(guide.contains(r##"- cor_background (cor, padrão: "#093D53"): "##));
+        assert!(guide
+            .contains(r#"- modo ("desligado" | "primario" | "replica", padrão: "desligado"): "#));
+        assert!(guide.contains("\n[[ganchos]] # "));
+        assert!(guide.contains("- tempo_limite_ms (inteiro, padrão: 5000): "));
+        assert!(guide.contains("- evento (\"start\" | \"pause\" | \"reset\" | \"threshold\" | \"zero\" | \"segment\", obrigatório): "));
+    }
+
+    #[test]
+    fn schema_describes_sections_and_nested_lists() {
+        let schema = json_schema(&Texts::new(&Default::default()));
+        let properties = &schema["properties"];
+        assert_eq!(properties["tempo"]["properties"]["tempo9"]["default"], 120);
+        assert_eq!(
+            properties["fontes"]["properties"]["fonte_gigante"]["minimum"],
+            1
+        );
+        assert_eq!(
+            properties["obs"]["properties"]["acoes"]["items"]["required"],
+            json!(["evento", "tipo"])
+        );
+        assert_eq!(
+            properties["idioma"]["properties"]["idioma"]["enum"],
+            json!(["pt-BR", "en", "es"])
+        );
+        assert_eq!(properties["segmentos"]["type"], "array");
+        assert_eq!(
+            properties["debug"]["properties"]["nivel"]["enum"],
+            json!(["desligado", "erro", "aviso", "info", "depuracao"])
+        );
+        for key in ["mostrar_qps", "mostrar_timer"] {
+            let legacy = &properties["debug"]["properties"][key];
+            assert_eq!(legacy["type"], "boolean");
+            assert_eq!(legacy["deprecated"], true);
+        }
+    }
+}
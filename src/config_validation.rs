@@ -0,0 +1,474 @@
+use crate::config_loader::{RegressivaConfig, Renderizador};
+use crate::i18n;
+use crate::logger;
+use crate::render;
+use crate::timer::format_timer;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Problema encontrado em um arquivo de configuração
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub line: Option<usize>,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+/// Erro de configuração legível por quem opera o programa, com arquivo, linha e campo
+#[derive(Debug)]
+pub struct ConfigError {
+    pub file: PathBuf,
+    pub problems: Vec<ConfigProblem>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Problemas em {}:", self.file.display())?;
+        for problem in &self.problems {
+            write!(f, "\n  - ")?;
+            if let Some(line) = problem.line {
+                write!(f, "linha {}: ", line)?;
+            }
+            if let Some(field) = &problem.field {
+                write!(f, "{}: ", field)?;
+            }
+            write!(f, "{}", problem.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Lê e valida o conteúdo de um arquivo de configuração
+pub fn parse(source: &str, file: &Path) -> Result<RegressivaConfig, ConfigError> {
+    // Chaves que nenhuma seção reconhece, em geral nomes digitados errado
+    let mut unknown = Vec::new();
+    let deserializer = toml::Deserializer::new(source);
+    let config: RegressivaConfig = serde_ignored::deserialize(deserializer, |path| {
+        unknown.push(key_path(&path));
+    })
+    .map_err(|e| {
+        let line = e.span().map(|span| line_of(source, span.start));
+        ConfigError {
+            file: file.to_path_buf(),
+            problems: vec![ConfigProblem {
+                line,
+                field: line.and_then(|line| field_at(source, line)),
+                message: e.message().to_string(),
+            }],
+        }
+    })?;
+
+    let mut problems: Vec<ConfigProblem> = unknown
+        .iter()
+        .filter_map(|keys| {
+            let (key, section) = keys.split_last()?;
+            Some(ConfigProblem {
+                line: locate(source, &section.join("."), key),
+                field: Some(keys.join(".")),
+                message: "chave desconhecida; verifique se o nome está correto".to_string(),
+            })
+        })
+        .collect();
+    problems.extend(validate(&config, source));
+    if problems.is_empty() {
+        Ok(config)
+    } else {
+        Err(ConfigError {
+            file: file.to_path_buf(),
+            problems,
+        })
+    }
+}
+
+/// Verifica regras que o formato TOML sozinho não garante
+pub fn validate(config: &RegressivaConfig, source: &str) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let mut report = |section: &str, key: &str, message: String| {
+        problems.push(ConfigProblem {
+            line: locate(source, section, key),
+            field: Some(format!("{}.{}", section, key)),
+            message,
+        });
+    };
+
+    let fontes = &config.fontes;
+    for (key, size) in [
+        ("fonte_gigante", fontes.get_fonte_gigante()),
+        ("fonte_grande", fontes.get_fonte_grande()),
+        ("fonte_media", fontes.get_fonte_media()),
+        ("fonte_pequena", fontes.get_fonte_pequena()),
+    ] {
+        if size == 0 {
+            report(
+                "fontes",
+                key,
+                "o tamanho da fonte deve ser maior que zero".to_string(),
+            );
+        }
+    }
+
+    // Com o alerta acima de todos os tempos, o display piscaria desde o início
+    let alerta = config.tempo.get_tempo_alerta_regressiva();
+    let maior_tempo = (1..=9)
+        .map(|i| config.tempo.get_tempo(i))
+        .max()
+        .unwrap_or(0);
+    if alerta >= maior_tempo {
+        report(
+            "tempo",
+            "tempo_alerta_regressiva",
+            format!(
+                "o alerta ({}s) deve ser menor que o maior tempo configurado ({}s)",
+                alerta, maior_tempo
+            ),
+        );
+    }
+
+    // Um rótulo em formato de tempo deve mostrar o tempo que o botão define; textos livres
+    // como "Réplica" não são conferidos
+    for i in 1..=9 {
+        let tempo = config.tempo.get_tempo(i);
+        let texto = config.tempo_texto.get_tempo_texto(i);
+        if label_seconds(texto).is_some_and(|segundos| segundos != tempo) {
+            report(
+                "tempo_texto",
+                &format!("tempo_texto{}", i),
+                format!(
+                    "o texto {:?} não corresponde a tempo{} = {} (esperado {:?})",
+                    texto,
+                    i,
+                    tempo,
+                    format_timer(Duration::from_secs(tempo))
+                ),
+            );
+        }
+    }
+
+    // Um nome digitado errado cairia no renderizador por software sem aviso
+    let renderizacao = &config.renderizacao;
+    for (key, renderizador) in [
+        ("janela_timer", renderizacao.get_janela_timer()),
+        ("janela_controle", renderizacao.get_janela_controle()),
+    ] {
+        if let Renderizador::Driver(name) = renderizador {
+            if let Err(message) = render::driver_index(name) {
+                report(
+                    "renderizacao",
+                    key,
+                    format!("{}; use acelerado, software ou um desses drivers", message),
+                );
+            }
+        }
+    }
+
+    // Com tamanho zero, o log seria girado a cada mensagem
+    if config.debug.get_tamanho_maximo() == 0 {
+        report(
+            "debug",
+            "tamanho_maximo_kb",
+            "o tamanho máximo do log deve ser maior que zero".to_string(),
+        );
+    }
+
+    // Uma categoria que não existe não tem efeito, mas não impede o programa de abrir
+    for categoria in config.debug.get_categorias().keys() {
+        if !logger::CATEGORIES.contains(&categoria.as_str()) {
+            log::warn!(
+                target: "config",
+                "Categoria de log desconhecida em [debug.categorias]: {:?}; use {}",
+                categoria,
+                logger::CATEGORIES.join(", ")
+            );
+        }
+    }
+
+    let idioma = config.idioma.get_idioma();
+    if !i18n::is_known_language(idioma) {
+        report(
+            "idioma",
+            "idioma",
+            format!("idioma desconhecido {:?}; use pt-BR, en ou es", idioma),
+        );
+    }
+    for key in config.idioma.get_textos().keys() {
+        if !i18n::is_known_key(key) {
+            report("idioma.textos", key, "texto desconhecido".to_string());
+        }
+    }
+
+    problems
+}
+
+// Nomes das seções e da chave de um caminho, sem os índices de listas como [[segmentos]]
+fn key_path(path: &serde_ignored::Path) -> Vec<String> {
+    match path {
+        serde_ignored::Path::Root => Vec::new(),
+        serde_ignored::Path::Map { parent, key } => {
+            let mut keys = key_path(parent);
+            keys.push(key.clone());
+            keys
+        }
+        serde_ignored::Path::Seq { parent, .. }
+        | serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => key_path(parent),
+    }
+}
+
+// Segundos de um rótulo escrito como MM:SS ou HH:MM:SS; outros textos retornam None
+fn label_seconds(texto: &str) -> Option<u64> {
+    let parts: Vec<&str> = texto.trim().split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    parts.iter().try_fold(0u64, |total, part| {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        total.checked_mul(60)?.checked_add(part.parse().ok()?)
+    })
+}
+
+// Número da linha (a partir de 1) de uma posição no texto
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset.min(source.len())].matches('\n').count() + 1
+}
+
+// Nome da seção de uma linha de cabeçalho como [fontes] ou [[segmentos]]
+fn section_header(line: &str) -> Option<&str> {
+    let line = line.trim();
+    line.strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .map(|name| name.trim_matches(|c| c == '[' || c == ']').trim())
+}
+
+// Nome da chave definida em uma linha como `chave = valor`
+fn key_of(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim().trim_matches('"');
+    (!key.is_empty() && !key.starts_with('#')).then_some(key)
+}
+
+// Encontra a linha em que a chave de uma seção é definida
+fn locate(source: &str, section: &str, key: &str) -> Option<usize> {
+    let mut current = "";
+    for (index, line) in source.lines().enumerate() {
+        if let Some(header) = section_header(line) {
+            current = header;
+        } else if current == section && key_of(line) == Some(key) {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+// Retorna o campo (secao.chave) definido em uma linha
+fn field_at(source: &str, line: usize) -> Option<String> {
+    let mut current = "";
+    for (index, text) in source.lines().enumerate().take(line) {
+        if let Some(header) = section_header(text) {
+            current = header;
+            if index + 1 == line {
+                return Some(current.to_string());
+            }
+        } else if index + 1 == line {
+            let key = key_of(text)?;
+            return Some(if current.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", current, key)
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_round_trips() {
+        let source = RegressivaConfig::default().to_toml_string().unwrap();
+        let config = parse(&source, Path::new("Config.toml")).unwrap();
+        assert_eq!(
+            config.cores.get_red_color(),
+            sdl2::pixels::Color::RGBA(100, 0, 0, 150)
+        );
+        assert!(source.contains(r##"cor_background = "#093D53""##));
+    }
+
+    #[test]
+    fn missing_sections_and_fields_use_defaults() {
+        let config = parse("[fontes]\nfonte_gigante = 96\n", Path::new("Config.toml")).unwrap();
+        assert_eq!(config.fontes.get_fonte_gigante(), 96);
+        assert_eq!(config.fontes.get_fonte_media(), 32);
+        assert_eq!(config.tempo.get_tempo(9), 120);
+        assert!(config.botoes.mostrar_botao_iniciar());
+    }
+
+    #[test]
+    fn reports_file_line_and_field() {
+        let source = "[cores]\ncor_texto = [0, 0, 300]\n";
+        let error = parse(source, Path::new("perfis/estadual.toml")).unwrap_err();
+        assert_eq!(error.problems[0].line, Some(2));
+        assert_eq!(error.problems[0].field.as_deref(), Some("cores.cor_texto"));
+        assert!(error
+            .to_string()
+            .starts_with("Problemas em perfis/estadual.toml:"));
+
+        let source = "[fontes]\nfonte_pequena = 0\n\n[tempo]\ntempo_alerta_regressiva = 200\n";
+        let error = parse(source, Path::new("Config.toml")).unwrap_err();
+        let fields: Vec<_> = error
+            .problems
+            .iter()
+            .map(|p| (p.line, p.field.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Some(2), "fontes.fonte_pequena"),
+                (Some(5), "tempo.tempo_alerta_regressiva")
+            ]
+        );
+    }
+
+    #[test]
+    fn time_labels_must_match_the_button_time() {
+        let source = "[tempo]\ntempo1 = 60\n\n[tempo_texto]\ntempo_texto1 = \"02:00\"\n";
+        let error = parse(source, Path::new("Config.toml")).unwrap_err();
+        assert_eq!(error.problems.len(), 1);
+        assert_eq!(
+            error.problems[0].field.as_deref(),
+            Some("tempo_texto.tempo_texto1")
+        );
+        assert_eq!(error.problems[0].line, Some(5));
+
+        let source = "[tempo]\ntempo1 = 3600\n\n[tempo_texto]\ntempo_texto1 = \"01:00:00\"\n";
+        assert!(parse(source, Path::new("Config.toml")).is_ok());
+    }
+
+    #[test]
+    fn reports_unknown_keys() {
+        let source = "[tempo]\ntempo_alerta = 10\n\n[cores]\ncor_fundo = \"black\"\n";
+        let error = parse(source, Path::new("Config.toml")).unwrap_err();
+        let fields: Vec<_> = error
+            .problems
+            .iter()
+            .map(|p| (p.line, p.field.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Some(2), "tempo.tempo_alerta"),
+                (Some(5), "cores.cor_fundo")
+            ]
+        );
+    }
+
+    #[test]
+    fn log_size_must_be_positive() {
+        let error = parse("[debug]\ntamanho_maximo_kb = 0\n", Path::new("Config.toml")).unwrap_err();
+        assert_eq!(
+            error.problems[0].field.as_deref(),
+            Some("debug.tamanho_maximo_kb")
+        );
+
+        let source = format!("[debug]\ntamanho_maximo_kb = {}\n", i64::MAX);
+        let config = parse(&source, Path::new("Config.toml")).unwrap();
+        assert_eq!(config.debug.get_tamanho_maximo(), u64::MAX);
+    }
+
+    #[test]
+    fn button_labels_are_free_text() {
+        let source = "[tempo_texto]\ntempo_texto1 = \"1 min\"\ntempo_texto2 = \"Réplica\"\n";
+        let config = parse(source, Path::new("Config.toml")).unwrap();
+        assert_eq!(config.tempo_texto.get_tempo_texto(2), "Réplica");
+    }
+
+    #[test]
+    fn old_debug_keys_become_categories() {
+        let source = "[debug]\nmostrar_qps = true\nmostrar_timer = false\n";
+        let config = parse(source, Path::new("Config.toml")).unwrap();
+        assert!(config.debug.depurar("qps"));
+        assert!(!config.debug.depurar("timer"));
+
+        // Ao salvar, as chaves antigas são gravadas no formato atual
+        let source = config.to_toml_string().unwrap();
+        assert!(!source.contains("mostrar_qps"));
+        let config = parse(&source, Path::new("Config.toml")).unwrap();
+        assert!(config.debug.depurar("qps"));
+    }
+
+    #[test]
+    fn renderer_names_ignore_case() {
+        let config: RegressivaConfig = toml::from_str(
+            "[renderizacao]\njanela_timer = \"Software\"\njanela_controle = \"opengl\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.renderizacao.get_janela_timer(),
+            &Renderizador::Software
+        );
+        assert_eq!(
+            config.renderizacao.get_janela_controle(),
+            &Renderizador::Driver("opengl".to_string())
+        );
+        let config: RegressivaConfig =
+            toml::from_str("[renderizacao]\njanela_timer = \"software\"\n").unwrap();
+        assert_eq!(
+            config.renderizacao.get_janela_timer(),
+            &Renderizador::Software
+        );
+        assert!(config
+            .to_toml_string()
+            .unwrap()
+            .contains("janela_timer = \"software\""));
+
+        let error = parse(
+            "[renderizacao]\njanela_timer = \"acelerada\"\n",
+            Path::new("Config.toml"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.problems[0].field.as_deref(),
+            Some("renderizacao.janela_timer")
+        );
+        assert_eq!(error.problems[0].line, Some(2));
+    }
+
+    #[test]
+    fn alert_must_be_below_the_longest_preset() {
+        let error = parse(
+            "[tempo]\ntempo_alerta_regressiva = 200\n",
+            Path::new("Config.toml"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.problems[0].field.as_deref(),
+            Some("tempo.tempo_alerta_regressiva")
+        );
+        assert_eq!(error.problems[0].line, Some(2));
+    }
+
+    #[test]
+    fn rejects_unknown_languages_and_texts() {
+        let source = "[idioma]\nidioma = \"fr\"\n\n[idioma.textos]\nbotao_iniciar = \"Go\"\nbotao_parar = \"Stop\"\n";
+        let error = parse(source, Path::new("Config.toml")).unwrap_err();
+        let fields: Vec<_> = error
+            .problems
+            .iter()
+            .map(|p| (p.line, p.field.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Some(2), "idioma.idioma"),
+                (Some(6), "idioma.textos.botao_parar")
+            ]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::config_loader::RegressivaConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Intervalo entre as verificações do arquivo de configuração
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Resultado de uma alteração detectada no arquivo
+pub enum Reload {
+    Applied(Box<RegressivaConfig>),
+    Rejected(String),
+}
+
+/// Observa o arquivo de configuração por polling para recarregá-lo sem reiniciar
+pub struct ConfigWatcher {
+    path: PathBuf,
+    interval: Duration,
+    last_checked: Instant,
+    last_seen: Option<(SystemTime, u64)>,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: &Path, interval: Duration) -> Self {
+        ConfigWatcher {
+            path: path.to_path_buf(),
+            interval,
+            last_checked: Instant::now(),
+            last_seen: file_version(path),
+        }
+    }
+
+    /// Relê o arquivo se ele mudou desde a última verificação
+    pub fn poll(&mut self) -> Option<Reload> {
+        if self.last_checked.elapsed() < self.interval {
+            return None;
+        }
+        self.last_checked = Instant::now();
+
+        // Data de modificação e tamanho, já que alguns sistemas têm datas pouco precisas
+        let version = file_version(&self.path);
+        if version.is_none() || version == self.last_seen {
+            return None;
+        }
+        self.last_seen = version;
+
+        Some(match RegressivaConfig::read_config(&self.path) {
+            Ok(config) => Reload::Applied(Box::new(config)),
+            Err(e) => Reload::Rejected(e.to_string()),
+        })
+    }
+}
+
+fn file_version(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reloads_valid_edits_and_rejects_invalid_ones() {
+        let path =
+            std::env::temp_dir().join(format!("regressiva-recarga-{}.toml", std::process::id()));
+        fs::write(&path, "[tempo_texto]\ntempo_texto9 = \"02:00\"\n").unwrap();
+        let mut watcher = ConfigWatcher::new(&path, Duration::ZERO);
+        assert!(watcher.poll().is_none());
+
+        fs::write(&path, "[creditos]\ncredito_texto = \"Debate estadual\"\n").unwrap();
+        match watcher.poll() {
+            Some(Reload::Applied(config)) => {
+                assert_eq!(config.creditos.get_credito_texto(), "Debate estadual")
+            }
+            _ => panic!("a alteração válida não foi aplicada"),
+        }
+        assert!(watcher.poll().is_none());
+
+        fs::write(&path, "[fontes]\nfonte_gigante = 0\n").unwrap();
+        match watcher.poll() {
+            Some(Reload::Rejected(message)) => assert!(message.contains("fontes.fonte_gigante")),
+            _ => panic!("a alteração inválida não foi rejeitada"),
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
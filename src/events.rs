@@ -0,0 +1,92 @@
+use crate::timer::{AlertStage, Countdown, TimerSnapshot, TimerStatus};
+use crate::timer_event::TimerEvent;
+
+/// Detecta eventos comparando estados consecutivos do timer
+#[derive(Default)]
+pub struct EventDetector {
+    last_snapshot: Option<TimerSnapshot>,
+    last_resets: u64,
+}
+
+impl EventDetector {
+    /// Retorna os eventos ocorridos desde o estado anterior
+    pub fn detect(&mut self, countdown: &Countdown, snapshot: &TimerSnapshot) -> Vec<TimerEvent> {
+        let mut events = Vec::new();
+
+        if let Some(last) = &self.last_snapshot {
+            let was_running = last.status == TimerStatus::Running;
+
+            if !was_running && snapshot.status == TimerStatus::Running {
+                events.push(TimerEvent::Start);
+            }
+            if was_running && snapshot.status == TimerStatus::Paused {
+                events.push(TimerEvent::Pause);
+            }
+            // O reset não muda necessariamente o estado exibido, por isso é contado no timer
+            if countdown.resets != self.last_resets {
+                events.push(TimerEvent::Reset);
+            }
+            if last.alert_stage == AlertStage::Normal && snapshot.alert_stage == AlertStage::Warning
+            {
+                events.push(TimerEvent::Threshold);
+            }
+            // Só conta como zero quando o tempo se esgota contando, não ao definir 00:00
+            if was_running && snapshot.alert_stage == AlertStage::Zero {
+                events.push(TimerEvent::Zero);
+            }
+            if snapshot.segment.is_some() && last.segment != snapshot.segment {
+                events.push(TimerEvent::Segment);
+            }
+        }
+
+        self.last_snapshot = Some(snapshot.clone());
+        self.last_resets = countdown.resets;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Command;
+    use crate::config_loader::RegressivaConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn detects_transitions_between_frames() {
+        let config = RegressivaConfig::default();
+        let mut detector = EventDetector::default();
+        let mut countdown = Countdown::new(Duration::new(60, 0));
+        assert!(detector
+            .detect(&countdown, &countdown.snapshot(&config))
+            .is_empty());
+
+        Command::Start.apply(&mut countdown, &config).unwrap();
+        assert_eq!(
+            detector.detect(&countdown, &countdown.snapshot(&config)),
+            vec![TimerEvent::Start]
+        );
+
+        countdown.duration = Duration::new(5, 0);
+        assert_eq!(
+            detector.detect(&countdown, &countdown.snapshot(&config)),
+            vec![TimerEvent::Threshold]
+        );
+
+        countdown.duration = Duration::ZERO;
+        countdown.is_running = false;
+        assert_eq!(
+            detector.detect(&countdown, &countdown.snapshot(&config)),
+            vec![TimerEvent::Zero]
+        );
+
+        Command::Reset.apply(&mut countdown, &config).unwrap();
+        assert_eq!(
+            detector.detect(&countdown, &countdown.snapshot(&config)),
+            vec![TimerEvent::Reset]
+        );
+        assert!(detector
+            .detect(&countdown, &countdown.snapshot(&config))
+            .is_empty());
+    }
+}
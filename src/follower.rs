@@ -0,0 +1,116 @@
+use crate::timer::TimerSnapshot;
+use std::io::ErrorKind;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::Message;
+
+/// Tempo sem notícias da instância remota para deixar de exibir o último estado
+const STALE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Espera entre tentativas de reconexão à instância remota
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Segue o estado transmitido pelo servidor WebSocket de uma instância remota
+pub struct Follower {
+    latest: Arc<Mutex<Option<(TimerSnapshot, Instant)>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Follower {
+    /// Conecta ao endereço (endereco:porta) em uma thread separada
+    pub fn start(address: &str) -> Self {
+        let follower = Follower {
+            latest: Arc::new(Mutex::new(None)),
+            stop: Arc::new(AtomicBool::new(false)),
+        };
+
+        let url = format!("ws://{}", address);
+        let latest = follower.latest.clone();
+        let stop = follower.stop.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                follow(&url, &latest, &stop);
+                thread::sleep(RECONNECT_DELAY);
+            }
+        });
+
+        follower
+    }
+
+    /// Retorna o último estado recebido, se ainda for recente
+    pub fn latest(&self) -> Option<TimerSnapshot> {
+        self.latest
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(_, received_at)| received_at.elapsed() <= STALE_TIMEOUT)
+            .map(|(snapshot, _)| snapshot.clone())
+    }
+}
+
+impl Drop for Follower {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// Recebe o estado até a conexão cair ou o seguidor ser encerrado
+fn follow(url: &str, latest: &Mutex<Option<(TimerSnapshot, Instant)>>, stop: &AtomicBool) {
+    let Ok((mut socket, _)) = tungstenite::connect(url) else {
+        return;
+    };
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        let _ = stream.set_read_timeout(Some(Duration::from_millis(100)));
+    }
+
+    while !stop.load(Ordering::Relaxed) {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                // Respostas a comandos de outros clientes não são estados e são ignoradas
+                if let Ok(snapshot) = serde_json::from_str::<TimerSnapshot>(&text) {
+                    *latest.lock().unwrap() = Some((snapshot, Instant::now()));
+                }
+            }
+            Ok(Message::Close(_)) => return,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+
+    let _ = socket.close(None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_loader::RegressivaConfig;
+    use crate::timer::Countdown;
+    use crate::ws_server;
+    use std::net::TcpListener;
+
+    #[test]
+    fn follows_the_remote_state_feed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let broadcaster = ws_server::serve(listener, None);
+        let follower = Follower::start(&addr);
+
+        let config = RegressivaConfig::default();
+        let mut countdown = Countdown::new(Duration::new(75, 0));
+        countdown.speaker = Some("Ana".to_string());
+        let snapshot = countdown.snapshot(&config);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while follower.latest().is_none() {
+            assert!(Instant::now() < deadline, "o seguidor não recebeu o estado");
+            broadcaster.publish(&serde_json::to_string(&snapshot).unwrap());
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(follower.latest(), Some(snapshot));
+    }
+}
@@ -0,0 +1,216 @@
+use crate::config_loader::FontesConfig;
+use sdl2::rwops::RWops;
+use sdl2::ttf::{Font, Sdl2TtfContext};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Fonte embutida no executável, usada quando nenhuma outra é encontrada (licença em assets/fonts)
+pub const EMBEDDED_FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSans-Bold.ttf");
+
+/// Diretório das fontes que acompanham o programa
+const FONTS_DIR: &str = "./fonts";
+
+/// Fontes comuns procuradas no sistema depois das configuradas
+const SYSTEM_FALLBACKS: [&str; 4] = [
+    "arial.ttf",
+    "DejaVuSans-Bold.ttf",
+    "DejaVuSans.ttf",
+    "LiberationSans-Regular.ttf",
+];
+
+/// Profundidade máxima da busca nos diretórios de fontes do sistema
+const MAX_SEARCH_DEPTH: usize = 4;
+
+/// Resultado da busca no sistema para cada lista de nomes, para que recarregar a configuração
+/// não percorra os diretórios de fontes de novo
+static SYSTEM_SEARCH: Mutex<BTreeMap<Vec<String>, Option<PathBuf>>> = Mutex::new(BTreeMap::new());
+
+/// Origem da fonte usada para desenhar os textos
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontSource {
+    File(PathBuf),
+    Embedded,
+}
+
+impl FontSource {
+    /// Descrição usada no log
+    pub fn describe(&self) -> String {
+        match self {
+            FontSource::File(path) => path.display().to_string(),
+            FontSource::Embedded => "DejaVu Sans Bold (embutida)".to_string(),
+        }
+    }
+}
+
+/// Procura a fonte na ordem: arquivo configurado, alternativas, diretórios do sistema e fonte embutida
+pub fn resolve(config: &FontesConfig) -> FontSource {
+    let names: Vec<&str> = std::iter::once(config.get_fonte())
+        .chain(config.get_alternativas().iter().map(String::as_str))
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    // Sem nenhuma fonte configurada, usa direto a embutida em vez das do sistema
+    if names.is_empty() {
+        return FontSource::Embedded;
+    }
+
+    // Caminhos relativos partem do diretório fonts; absolutos são usados como estão
+    if let Some(path) = names
+        .iter()
+        .map(|name| Path::new(FONTS_DIR).join(name))
+        .find(|path| path.is_file())
+    {
+        return FontSource::File(path);
+    }
+
+    let file_names: Vec<String> = names
+        .iter()
+        .filter_map(|name| Path::new(name).file_name())
+        .filter_map(|name| name.to_str())
+        .chain(SYSTEM_FALLBACKS)
+        .map(str::to_string)
+        .collect();
+    let mut cache = SYSTEM_SEARCH.lock().unwrap_or_else(|e| e.into_inner());
+    let found = cache
+        .entry(file_names)
+        .or_insert_with_key(|file_names| search_system(file_names));
+    match found {
+        Some(path) => FontSource::File(path.clone()),
+        None => FontSource::Embedded,
+    }
+}
+
+/// Carrega a fonte no tamanho pedido, recorrendo à fonte embutida se o arquivo for inválido
+pub fn load<'ttf>(
+    ttf_context: &'ttf Sdl2TtfContext,
+    source: &FontSource,
+    size: u16,
+) -> Result<Font<'ttf, 'static>, String> {
+    if let FontSource::File(path) = source {
+        match ttf_context.load_font(path, size) {
+            Ok(font) => return Ok(font),
+            Err(e) => log::warn!("Falha ao carregar a fonte {}: {}", path.display(), e),
+        }
+    }
+    ttf_context.load_font_from_rwops(RWops::from_bytes(EMBEDDED_FONT)?, size)
+}
+
+// Diretórios onde os sistemas operacionais instalam fontes
+fn system_font_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![
+        PathBuf::from("C:\\Windows\\Fonts"),
+        PathBuf::from("/usr/share/fonts"),
+        PathBuf::from("/usr/local/share/fonts"),
+        PathBuf::from("/Library/Fonts"),
+        PathBuf::from("/System/Library/Fonts"),
+    ];
+    if let Some(home) = std::env::var_os("HOME") {
+        let home = PathBuf::from(home);
+        dirs.push(home.join(".local/share/fonts"));
+        dirs.push(home.join(".fonts"));
+        dirs.push(home.join("Library/Fonts"));
+    }
+    if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+        dirs.push(PathBuf::from(local).join("Microsoft\\Windows\\Fonts"));
+    }
+    dirs
+}
+
+// Procura os arquivos nos diretórios do sistema, percorrendo cada diretório uma única vez;
+// o primeiro nome da lista que for encontrado vence
+fn search_system(file_names: &[String]) -> Option<PathBuf> {
+    let mut found = vec![None; file_names.len()];
+    for dir in system_font_dirs() {
+        search_dir(&dir, file_names, MAX_SEARCH_DEPTH, &mut found);
+        if found[0].is_some() {
+            break;
+        }
+    }
+    found.into_iter().flatten().next()
+}
+
+// Registra, sem diferenciar maiúsculas, onde cada nome aparece pela primeira vez nos subdiretórios
+fn search_dir(dir: &Path, file_names: &[String], depth: usize, found: &mut [Option<PathBuf>]) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        } else if let Some(name) = entry.file_name().to_str() {
+            let index = file_names
+                .iter()
+                .position(|file_name| name.eq_ignore_ascii_case(file_name));
+            if let Some(slot) = index.map(|index| &mut found[index]) {
+                slot.get_or_insert(path);
+            }
+        }
+    }
+
+    if depth == 0 {
+        return;
+    }
+    subdirs.sort();
+    for subdir in subdirs {
+        search_dir(&subdir, file_names, depth - 1, found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn searches_nested_font_directories() {
+        let root = std::env::temp_dir().join(format!("regressiva-fontes-{}", std::process::id()));
+        let nested = root.join("truetype").join("marca");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("Marca-Bold.TTF"), b"").unwrap();
+
+        fs::write(root.join("outra.ttf"), b"").unwrap();
+
+        let names = ["marca-bold.ttf".to_string(), "outra.ttf".to_string()];
+        let mut found = vec![None; 2];
+        search_dir(&root, &names, MAX_SEARCH_DEPTH, &mut found);
+        assert_eq!(
+            found,
+            vec![Some(nested.join("Marca-Bold.TTF")), Some(root.join("outra.ttf"))]
+        );
+
+        let mut found = vec![None; 2];
+        search_dir(&root, &names, 0, &mut found);
+        assert_eq!(found, vec![None, Some(root.join("outra.ttf"))]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn configured_absolute_paths_are_used_directly() {
+        let file =
+            std::env::temp_dir().join(format!("regressiva-fonte-{}.ttf", std::process::id()));
+        fs::write(&file, EMBEDDED_FONT).unwrap();
+
+        let config: FontesConfig = toml::from_str(&format!(
+            "fonte = \"inexistente.ttf\"\nalternativas = [{:?}]",
+            file
+        ))
+        .unwrap();
+        assert_eq!(resolve(&config), FontSource::File(file.clone()));
+
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn uses_the_embedded_font_when_none_is_configured() {
+        assert_eq!(resolve(&FontesConfig::default()), FontSource::Embedded);
+    }
+
+    #[test]
+    fn embedded_font_is_a_truetype_file() {
+        assert_eq!(&EMBEDDED_FONT[..4], &[0, 1, 0, 0]);
+    }
+}
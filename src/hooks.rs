@@ -0,0 +1,140 @@
+use crate::config_loader::GanchoConfig;
+use crate::timer_event::TimerEvent;
+use crate::timer::TimerSnapshot;
+use serde_json::json;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Intervalo entre as verificações do término de um gancho
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Executa comandos externos configurados em `[[ganchos]]` nos eventos do timer
+pub struct Hooks {
+    ganchos: Vec<GanchoConfig>,
+}
+
+impl Hooks {
+    pub fn new(ganchos: &[GanchoConfig]) -> Self {
+        Hooks {
+            ganchos: ganchos.to_vec(),
+        }
+    }
+
+    /// Dispara os ganchos de cada evento em threads separadas, sem bloquear o loop principal
+    pub fn run(&self, events: &[TimerEvent], snapshot: &TimerSnapshot) {
+        for event in events {
+            for gancho in self.ganchos.iter().filter(|g| g.get_evento() == *event) {
+                let gancho = gancho.clone();
+                let event = *event;
+                let snapshot = snapshot.clone();
+                thread::spawn(move || {
+                    match execute(&gancho, event, &snapshot) {
+                        Ok(true) => {}
+                        Ok(false) => log::warn!(
+                            "Gancho {:?} encerrado por exceder o tempo limite",
+                            gancho.get_comando()
+                        ),
+                        Err(e) => log::warn!(
+                            "Falha ao executar o gancho {:?}: {}",
+                            gancho.get_comando(),
+                            e
+                        ),
+                    }
+                });
+            }
+        }
+    }
+}
+
+// Executa o gancho e espera o término; retorna falso se o tempo limite estourou
+fn execute(gancho: &GanchoConfig, event: TimerEvent, snapshot: &TimerSnapshot) -> io::Result<bool> {
+    let mut child = Command::new(gancho.get_comando())
+        .args(gancho.get_argumentos())
+        .env("REGRESSIVA_EVENTO", event.label())
+        .env("REGRESSIVA_RESTANTE_MS", snapshot.remaining_ms.to_string())
+        .env("REGRESSIVA_TEXTO", &snapshot.text)
+        .env("REGRESSIVA_STATUS", snapshot.status.label())
+        .env("REGRESSIVA_ALERTA", snapshot.alert_stage.label())
+        .env(
+            "REGRESSIVA_ORADOR",
+            snapshot.speaker.as_deref().unwrap_or_default(),
+        )
+        .env(
+            "REGRESSIVA_SEGMENTO",
+            snapshot.segment.as_deref().unwrap_or_default(),
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // O estado também vai em JSON pela entrada padrão; o programa pode ignorá-la
+    if let Some(mut stdin) = child.stdin.take() {
+        let payload = json!({ "event": event, "state": snapshot });
+        let _ = stdin.write_all(payload.to_string().as_bytes());
+    }
+
+    let deadline = Instant::now() + gancho.get_tempo_limite();
+    loop {
+        if child.try_wait()?.is_some() {
+            return Ok(true);
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(false);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::config_loader::RegressivaConfig;
+    use crate::timer::Countdown;
+    use std::fs;
+
+    #[test]
+    fn passes_state_through_environment_and_stdin() {
+        let output = std::env::temp_dir().join(format!("regressiva-gancho-{}", std::process::id()));
+        let script = format!(
+            "echo \"$REGRESSIVA_EVENTO $REGRESSIVA_TEXTO $REGRESSIVA_ORADOR\" > {0:?}; cat >> {0:?}",
+            output
+        );
+        let gancho: GanchoConfig = toml::from_str(&format!(
+            "evento = \"zero\"\ncomando = \"sh\"\nargumentos = [\"-c\", {:?}]",
+            script
+        ))
+        .unwrap();
+
+        let mut countdown = Countdown::new(Duration::ZERO);
+        countdown.speaker = Some("Ana".to_string());
+        let snapshot = countdown.snapshot(&RegressivaConfig::default());
+        assert!(execute(&gancho, TimerEvent::Zero, &snapshot).unwrap());
+
+        let contents = fs::read_to_string(&output).unwrap();
+        let (env_line, stdin_json) = contents.split_once('\n').unwrap();
+        assert_eq!(env_line, "zero 00:00 Ana");
+        let payload: serde_json::Value = serde_json::from_str(stdin_json).unwrap();
+        assert_eq!(payload["event"], "zero");
+        assert_eq!(payload["state"]["speaker"], "Ana");
+
+        fs::remove_file(&output).unwrap();
+    }
+
+    #[test]
+    fn kills_hooks_that_exceed_the_timeout() {
+        let gancho: GanchoConfig = toml::from_str(
+            "evento = \"start\"\ncomando = \"sleep\"\nargumentos = [\"5\"]\ntempo_limite_ms = 100",
+        )
+        .unwrap();
+        let snapshot = Countdown::new(Duration::new(30, 0)).snapshot(&RegressivaConfig::default());
+
+        let started = Instant::now();
+        assert!(!execute(&gancho, TimerEvent::Start, &snapshot).unwrap());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,424 @@
+use crate::commands::{self, Command, RemoteRequest};
+use crate::config_loader::HttpConfig;
+use crate::overlay::Overlay;
+use crate::timer::TimerSnapshot;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Take, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Tamanho máximo aceito para o corpo de uma requisição
+const MAX_BODY: usize = 4 * 1024;
+
+/// Tamanho máximo da linha de requisição somada aos cabeçalhos
+const MAX_HEADER: u64 = 8 * 1024;
+
+/// Quantidade máxima de linhas de cabeçalho
+const MAX_HEADER_LINES: usize = 64;
+
+/// Último estado do timer, consultado por `GET /status`
+#[derive(Clone, Default)]
+pub struct StatusHandle {
+    snapshot: Arc<Mutex<Option<TimerSnapshot>>>,
+}
+
+impl StatusHandle {
+    /// Atualiza o estado exposto pela API
+    pub fn update(&self, snapshot: &TimerSnapshot) {
+        *self.snapshot.lock().unwrap() = Some(snapshot.clone());
+    }
+
+    fn get(&self) -> Option<TimerSnapshot> {
+        self.snapshot.lock().unwrap().clone()
+    }
+}
+
+// Requisição HTTP já interpretada
+struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    body: String,
+}
+
+// Resposta HTTP a ser enviada
+struct Response {
+    code: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(code: u16, body: String) -> Self {
+        Response {
+            code,
+            content_type: "application/json; charset=utf-8",
+            body: body.into_bytes(),
+        }
+    }
+
+    fn error(code: u16, message: &str) -> Self {
+        Response::json(code, json!({ "ok": false, "error": message }).to_string())
+    }
+}
+
+/// Inicia o servidor HTTP em uma thread separada
+pub fn start(
+    config: &HttpConfig,
+    overlay: Overlay,
+    commands: Sender<RemoteRequest>,
+) -> io::Result<StatusHandle> {
+    let listener = TcpListener::bind(config.get_endereco())?;
+    Ok(serve(listener, overlay, commands))
+}
+
+/// Atende as requisições do listener, uma thread por conexão
+pub fn serve(
+    listener: TcpListener,
+    overlay: Overlay,
+    commands: Sender<RemoteRequest>,
+) -> StatusHandle {
+    let status = StatusHandle::default();
+    let overlay = Arc::new(overlay);
+
+    let accept_status = status.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let status = accept_status.clone();
+            let overlay = overlay.clone();
+            let commands = commands.clone();
+            thread::spawn(move || handle_connection(stream, &status, &overlay, &commands));
+        }
+    });
+
+    status
+}
+
+// Lê uma requisição, responde e fecha a conexão
+fn handle_connection(
+    mut stream: TcpStream,
+    status: &StatusHandle,
+    overlay: &Overlay,
+    commands: &Sender<RemoteRequest>,
+) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+
+    let response = match read_request(&mut stream) {
+        Ok(request) => route(&request, status, overlay, commands),
+        Err(response) => response,
+    };
+
+    let _ = write_response(&mut stream, &response);
+
+    // Descarta o que o cliente ainda enviou (uma requisição recusada pelo tamanho, por exemplo);
+    // fechar com dados não lidos faria o sistema responder com RST e a resposta se perderia
+    let _ = stream.shutdown(Shutdown::Write);
+    let _ = io::copy(
+        &mut (&stream).take(MAX_HEADER + MAX_BODY as u64),
+        &mut io::sink(),
+    );
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let mut reader = BufReader::new(stream.take(MAX_HEADER));
+
+    let mut request_line = String::new();
+    read_head_line(&mut reader, &mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "linha de requisição inválida"));
+    };
+
+    let mut content_length = 0;
+    for lines in 0.. {
+        let mut header = String::new();
+        read_head_line(&mut reader, &mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if lines == MAX_HEADER_LINES {
+            return Err(Response::error(431, "cabeçalhos demais"));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Response::error(400, "Content-Length inválido"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Err(Response::error(413, "corpo da requisição muito grande"));
+    }
+    reader.get_mut().set_limit(content_length as u64);
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(400, "corpo da requisição incompleto"))?;
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (target, None),
+    };
+
+    Ok(Request {
+        method: method.to_uppercase(),
+        path: path.trim_end_matches('/').to_string(),
+        query,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+// Lê uma linha da requisição, recusando linhas que passem do limite dos cabeçalhos
+fn read_head_line(
+    reader: &mut BufReader<Take<&mut TcpStream>>,
+    line: &mut String,
+) -> Result<(), Response> {
+    reader
+        .read_line(line)
+        .map_err(|_| Response::error(400, "requisição inválida"))?;
+    if line.ends_with('\n') {
+        Ok(())
+    } else if reader.get_ref().limit() == 0 {
+        Err(Response::error(431, "cabeçalhos grandes demais"))
+    } else {
+        Err(Response::error(400, "requisição incompleta"))
+    }
+}
+
+// Direciona a requisição para o comando correspondente
+fn route(
+    request: &Request,
+    status: &StatusHandle,
+    overlay: &Overlay,
+    commands: &Sender<RemoteRequest>,
+) -> Response {
+    let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let command = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["status"]) => {
+            return match status.get() {
+                Some(snapshot) => Response::json(200, json!(snapshot).to_string()),
+                None => Response::error(503, "estado ainda não disponível"),
+            };
+        }
+        ("GET", ["overlay"]) => {
+            return Response {
+                code: 200,
+                content_type: "text/html; charset=utf-8",
+                body: overlay.page().as_bytes().to_vec(),
+            };
+        }
+        ("GET", ["overlay", "fonte.ttf"]) => {
+            return Response {
+                code: 200,
+                content_type: "font/ttf",
+                body: overlay.font().to_vec(),
+            };
+        }
+        ("POST", ["start"]) => Ok(Command::Start),
+        ("POST", ["pause"]) => Ok(Command::Pause),
+        ("POST", ["reset"]) => Ok(Command::Reset),
+        ("POST", ["set"]) => seconds_param(request)
+            .and_then(|seconds| {
+                u64::try_from(seconds).map_err(|_| "seconds não pode ser negativo".to_string())
+            })
+            .map(|seconds| Command::Set { seconds }),
+        ("POST", ["preset", index]) => index
+            .parse()
+            .map(|index| Command::Preset { index })
+            .map_err(|_| format!("preset inválido: {}", index)),
+        ("POST", ["nudge"]) => seconds_param(request).map(|seconds| Command::Nudge { seconds }),
+        (_, ["status" | "start" | "pause" | "reset" | "set" | "nudge"]) | (_, ["preset", _]) => {
+            return Response::error(405, "método não permitido");
+        }
+        _ => return Response::error(404, "rota não encontrada"),
+    };
+
+    let command = match command {
+        Ok(command) => command,
+        Err(e) => return Response::error(400, &e),
+    };
+
+    let name = command.name();
+    match commands::send_command(commands, command) {
+        Ok(()) => Response::json(200, json!({ "ok": true, "command": name }).to_string()),
+        Err(e) => Response::error(400, &e),
+    }
+}
+
+// Lê o parâmetro `seconds` da query string ou do corpo (JSON ou número puro)
+fn seconds_param(request: &Request) -> Result<i64, String> {
+    let from_query = request.query.as_deref().and_then(|query| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "seconds")
+            .map(|(_, value)| value.to_string())
+    });
+
+    let value = match from_query {
+        Some(value) => value,
+        None => {
+            let body = request.body.trim();
+            match serde_json::from_str::<Value>(body) {
+                Ok(Value::Object(object)) => object
+                    .get("seconds")
+                    .map(|seconds| seconds.to_string())
+                    .unwrap_or_default(),
+                _ => body.to_string(),
+            }
+        }
+    };
+
+    value
+        .parse()
+        .map_err(|_| "parâmetro seconds ausente ou inválido".to_string())
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let reason = match response.code {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+        response.code,
+        reason,
+        response.content_type,
+        response.body.len(),
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_loader::RegressivaConfig;
+    use crate::timer::Countdown;
+    use std::sync::mpsc;
+
+    // Envia uma requisição crua e retorna o código e o corpo da resposta
+    fn raw_request(addr: std::net::SocketAddr, raw: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let code = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (code, body.to_string())
+    }
+
+    fn request(addr: std::net::SocketAddr, raw: &str) -> (u16, Value) {
+        let (code, body) = raw_request(addr, raw);
+        (code, serde_json::from_str(&body).unwrap())
+    }
+
+    #[test]
+    fn http_routes_drive_the_countdown() {
+        let countdown = Arc::new(Mutex::new(Countdown::new(Duration::new(30, 0))));
+        let (tx, rx) = mpsc::channel::<RemoteRequest>();
+        let engine_countdown = countdown.clone();
+        thread::spawn(move || {
+            let config = RegressivaConfig::default();
+            for request in rx {
+                let result = request
+                    .command
+                    .apply(&mut engine_countdown.lock().unwrap(), &config);
+                let _ = request.reply.send(result);
+            }
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let overlay = Overlay::from_config(&RegressivaConfig::default());
+        let status = serve(listener, overlay, tx);
+
+        let (code, _) = request(addr, "GET /status HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 503);
+
+        let config = RegressivaConfig::default();
+        status.update(&countdown.lock().unwrap().snapshot(&config));
+        let (code, body) = request(addr, "GET /status HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 200);
+        assert_eq!(body["text"], "00:30");
+
+        let (code, _) = request(addr, "POST /set?seconds=90 HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 200);
+        assert_eq!(countdown.lock().unwrap().duration, Duration::new(90, 0));
+
+        let (code, _) = request(
+            addr,
+            "POST /nudge HTTP/1.1\r\nContent-Length: 16\r\n\r\n{\"seconds\": -30}",
+        );
+        assert_eq!(code, 200);
+        assert_eq!(countdown.lock().unwrap().duration, Duration::new(60, 0));
+
+        let (code, body) = request(addr, "POST /preset/2 HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 200);
+        assert_eq!(body["command"], "preset");
+        assert_eq!(countdown.lock().unwrap().duration, Duration::new(15, 0));
+
+        let (code, _) = request(addr, "POST /preset/10 HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 400);
+        let (code, _) = request(addr, "GET /start HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 405);
+        let (code, _) = request(addr, "POST /unknown HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 404);
+
+        let (code, _) = request(
+            addr,
+            "POST /nudge HTTP/1.1\r\nContent-Length: 18446744073709551615\r\n\r\n",
+        );
+        assert_eq!(code, 413);
+        let (code, _) = request(
+            addr,
+            "POST /nudge HTTP/1.1\r\nContent-Length: muito\r\n\r\n",
+        );
+        assert_eq!(code, 400);
+
+        let long_header = format!(
+            "GET /status HTTP/1.1\r\nX-Longo: {}\r\n\r\n",
+            "a".repeat(10_000)
+        );
+        let (code, _) = request(addr, &long_header);
+        assert_eq!(code, 431);
+        let many_headers = format!(
+            "GET /status HTTP/1.1\r\n{}\r\n",
+            "X-Outro: 1\r\n".repeat(100)
+        );
+        let (code, _) = request(addr, &many_headers);
+        assert_eq!(code, 431);
+    }
+
+    #[test]
+    fn overlay_page_is_served_with_configured_style() {
+        let (tx, _rx) = mpsc::channel::<RemoteRequest>();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let overlay = Overlay::from_config(&RegressivaConfig::default());
+        let _status = serve(listener, overlay, tx);
+
+        let (code, body) = raw_request(addr, "GET /overlay HTTP/1.1\r\n\r\n");
+        assert_eq!(code, 200);
+        assert!(body.contains("background: transparent"));
+        assert!(body.contains(r#""cor_alerta":"rgb(255, 0, 0)""#));
+        assert!(!body.contains("/*ESTILO*/"));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::config_loader::IdiomaConfig;
+use std::collections::BTreeMap;
+use toml::{Table, Value};
+
+/// Idioma usado quando nenhum outro é configurado
+pub const DEFAULT_LANGUAGE: &str = "pt-BR";
+
+/// Idiomas com arquivo embutido no executável
+pub const LANGUAGES: [(&str, &str); 3] = [
+    ("pt-BR", include_str!("../assets/lang/pt-BR.toml")),
+    ("en", include_str!("../assets/lang/en.toml")),
+    ("es", include_str!("../assets/lang/es.toml")),
+];
+
+/// Textos da interface no idioma configurado, já com as substituições do operador
+#[derive(Debug, Clone)]
+pub struct Texts {
+    texts: BTreeMap<String, String>,
+}
+
+impl Texts {
+    /// Monta os textos: português como base, o idioma escolhido por cima e, por último, os textos próprios
+    pub fn new(config: &IdiomaConfig) -> Self {
+        let mut texts = parse(DEFAULT_LANGUAGE);
+        texts.extend(parse(config.get_idioma()));
+        texts.extend(
+            config
+                .get_textos()
+                .iter()
+                .map(|(key, text)| (key.clone(), text.clone())),
+        );
+        Texts { texts }
+    }
+
+    /// Retorna o texto da chave, ou a própria chave se ela não existir
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.lookup(key).unwrap_or(key)
+    }
+
+    /// Retorna o texto da chave, se ela existir
+    pub fn lookup(&self, key: &str) -> Option<&str> {
+        self.texts.get(key).map(String::as_str)
+    }
+
+    /// Retorna o texto da chave trocando cada {nome} pelo valor correspondente
+    pub fn format(&self, key: &str, values: &[(&str, &str)]) -> String {
+        values
+            .iter()
+            .fold(self.get(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+}
+
+/// Indica se há arquivo embutido para o idioma
+pub fn is_known_language(language: &str) -> bool {
+    source(language).is_some()
+}
+
+/// Indica se a chave existe nos arquivos de idioma
+pub fn is_known_key(key: &str) -> bool {
+    parse(DEFAULT_LANGUAGE).contains_key(key)
+}
+
+/// Chaves de todos os textos, em ordem alfabética
+pub fn keys() -> Vec<String> {
+    parse(DEFAULT_LANGUAGE).into_keys().collect()
+}
+
+// Conteúdo do arquivo do idioma, sem diferenciar maiúsculas (pt-br, EN)
+fn source(language: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(language))
+        .map(|(_, source)| *source)
+}
+
+// Lê as chaves e textos de um arquivo de idioma embutido
+fn parse(language: &str) -> BTreeMap<String, String> {
+    let mut texts = BTreeMap::new();
+    if let Some(table) = source(language).and_then(|source| source.parse::<Table>().ok()) {
+        flatten("", table, &mut texts);
+    }
+    texts
+}
+
+// As tabelas do arquivo, como [guia.tempo], viram chaves com pontos: guia.tempo.tempo_reset
+fn flatten(prefix: &str, table: Table, texts: &mut BTreeMap<String, String>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::String(text) => {
+                texts.insert(key, text);
+            }
+            Value::Table(table) => flatten(&key, table, texts),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_language_has_every_key() {
+        let base = parse(DEFAULT_LANGUAGE);
+        assert!(base.contains_key("boas_vindas"));
+        assert!(base.contains_key("guia.tempo.tempo_reset"));
+        for (code, _) in LANGUAGES {
+            let texts = parse(code);
+            assert_eq!(
+                texts.keys().collect::<Vec<_>>(),
+                base.keys().collect::<Vec<_>>(),
+                "chaves diferentes em {}",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn overrides_replace_language_texts() {
+        let config: IdiomaConfig = toml::from_str(
+            "idioma = \"EN\"\n[textos]\nbotao_iniciar = \"Go\"\nalteracao_rejeitada = \"Nope: {motivo}\"\n",
+        )
+        .unwrap();
+        let texts = Texts::new(&config);
+        assert_eq!(texts.get("botao_iniciar"), "Go");
+        assert_eq!(texts.get("botao_pausar"), "Pause");
+        assert_eq!(
+            texts.format("alteracao_rejeitada", &[("motivo", "linha 2")]),
+            "Nope: linha 2"
+        );
+        assert_eq!(texts.get("chave_inexistente"), "chave_inexistente");
+
+        let texts = Texts::new(&IdiomaConfig::default());
+        assert_eq!(texts.format("ajustes_tempo", &[("n", "3")]), "Tempo 3 (s)");
+    }
+}
@@ -0,0 +1,299 @@
+use crate::config_loader::{DebugConfig, NivelLog};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Categorias que registram mensagens: os módulos que usam log, os alvos explícitos (config,
+/// fonts, qps, timer) e a biblioteca de WebSocket
+pub const CATEGORIES: [&str; 8] = [
+    "config",
+    "fonts",
+    "hooks",
+    "obs",
+    "qps",
+    "render",
+    "timer",
+    "tungstenite",
+];
+
+impl From<NivelLog> for LevelFilter {
+    fn from(nivel: NivelLog) -> Self {
+        match nivel {
+            NivelLog::Desligado => LevelFilter::Off,
+            NivelLog::Erro => LevelFilter::Error,
+            NivelLog::Aviso => LevelFilter::Warn,
+            NivelLog::Info => LevelFilter::Info,
+            NivelLog::Depuracao => LevelFilter::Debug,
+        }
+    }
+}
+
+/// Logger com nível por categoria, gravando em arquivo com rotação e, se configurado, no stderr
+pub struct Logger {
+    state: Mutex<State>,
+}
+
+struct State {
+    level: LevelFilter,
+    categories: BTreeMap<String, LevelFilter>,
+    console: bool,
+    file: Option<RotatingFile>,
+}
+
+impl State {
+    // Nível da categoria: o módulo para regressiva::fonts e o primeiro trecho para bibliotecas
+    // como tungstenite::protocol
+    fn level_for(&self, target: &str) -> LevelFilter {
+        let target = target
+            .strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::"))
+            .unwrap_or(target);
+        let category = target.split("::").next().unwrap_or(target);
+        self.categories
+            .get(category)
+            .copied()
+            .unwrap_or(self.level)
+    }
+}
+
+/// Instala o logger na primeira chamada e aplica a configuração; pode ser chamada de novo ao recarregar
+pub fn configure(config: &DebugConfig) {
+    let logger = LOGGER.get_or_init(|| Logger {
+        state: Mutex::new(State {
+            level: LevelFilter::Off,
+            categories: BTreeMap::new(),
+            console: false,
+            file: None,
+        }),
+    });
+    // Só falha se já estiver instalado
+    let _ = log::set_logger(logger);
+
+    let Ok(mut state) = logger.state.lock() else {
+        return;
+    };
+    state.level = config.get_nivel().into();
+    state.categories = config
+        .get_categorias()
+        .iter()
+        .map(|(category, nivel)| (category.clone(), (*nivel).into()))
+        .collect();
+    state.console = config.console();
+
+    let path = Path::new(config.get_arquivo());
+    state.file = match state.file.take() {
+        _ if config.get_arquivo().is_empty() => None,
+        Some(mut file) if file.path == path => {
+            file.max_size = config.get_tamanho_maximo();
+            file.backups = config.get_arquivos_antigos();
+            Some(file)
+        }
+        _ => Some(RotatingFile::new(
+            path,
+            config.get_tamanho_maximo(),
+            config.get_arquivos_antigos(),
+        )),
+    };
+
+    let max_level = state
+        .categories
+        .values()
+        .copied()
+        .fold(state.level, Ord::max);
+    log::set_max_level(max_level);
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.state
+            .lock()
+            .is_ok_and(|state| metadata.level() <= state.level_for(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if record.level() > state.level_for(record.target()) {
+            return;
+        }
+
+        let line = format!(
+            "{} {:<5} [{}] {}\n",
+            timestamp(SystemTime::now()),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        // Falhas ao gravar o log nunca derrubam o programa
+        if let Some(file) = state.file.as_mut() {
+            let _ = file.write(&line);
+        }
+        if state.console {
+            let _ = io::stderr().write_all(line.as_bytes());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Arquivo de log renomeado para .1, .2... ao atingir o tamanho máximo
+pub struct RotatingFile {
+    path: PathBuf,
+    max_size: u64,
+    backups: u32,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn new(path: &Path, max_size: u64, backups: u32) -> Self {
+        RotatingFile {
+            path: path.to_path_buf(),
+            max_size,
+            backups,
+            file: None,
+            size: 0,
+        }
+    }
+
+    /// Acrescenta uma linha, girando os arquivos antes se ela ultrapassar o tamanho máximo
+    pub fn write(&mut self, line: &str) -> io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        if self.file.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            self.size = file.metadata()?.len();
+            self.file = Some(file);
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(line.as_bytes())?;
+            self.size += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    // regressiva.log vira regressiva.log.1, o .1 vira .2 e o mais antigo é descartado
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        self.size = 0;
+        if self.backups == 0 {
+            return fs::remove_file(&self.path);
+        }
+        for index in (1..self.backups).rev() {
+            let from = self.backup_path(index);
+            if from.exists() {
+                fs::rename(from, self.backup_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.backup_path(1))
+    }
+
+    fn backup_path(&self, index: u32) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+}
+
+// Data e hora em UTC no formato ISO 8601, sem depender do fuso configurado no sistema
+fn timestamp(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = elapsed.as_secs();
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+
+    // Conversão de dias desde 1970-01-01 para data civil (algoritmo de Howard Hinnant)
+    let z = days as i64 + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_utc_timestamps() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let time = UNIX_EPOCH + Duration::from_millis(1_792_411_445_123);
+        assert_eq!(timestamp(time), "2026-10-19T12:04:05.123Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(1_709_164_800);
+        assert_eq!(timestamp(leap_day), "2024-02-29T00:00:00.000Z");
+    }
+
+    #[test]
+    fn rotates_files_keeping_the_configured_backups() {
+        let dir = std::env::temp_dir().join(format!("regressiva-log-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("regressiva.log");
+        let mut file = RotatingFile::new(&path, 20, 2);
+
+        for line in ["primeira linha\n", "segunda linha\n", "terceira linha\n", "quarta linha\n"] {
+            file.write(line).unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "quarta linha\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("regressiva.log.1")).unwrap(),
+            "terceira linha\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("regressiva.log.2")).unwrap(),
+            "segunda linha\n"
+        );
+        assert!(!dir.join("regressiva.log.3").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn categories_override_the_default_level() {
+        let state = State {
+            level: LevelFilter::Info,
+            categories: [
+                ("qps".to_string(), LevelFilter::Debug),
+                ("fonts".to_string(), LevelFilter::Warn),
+            ]
+            .into(),
+            console: false,
+            file: None,
+        };
+        assert_eq!(state.level_for("qps"), LevelFilter::Debug);
+        assert_eq!(state.level_for("config"), LevelFilter::Info);
+        assert_eq!(state.level_for("regressiva::qps"), LevelFilter::Debug);
+        // O alvo explícito "fonts" e o módulo regressiva::fonts caem na mesma categoria
+        assert_eq!(state.level_for("fonts"), LevelFilter::Warn);
+        assert_eq!(state.level_for("regressiva::fonts"), LevelFilter::Warn);
+        assert_eq!(
+            state.level_for("tungstenite::protocol"),
+            LevelFilter::Info
+        );
+    }
+}
@@ -6,45 +6,181 @@ use sdl2::mouse::MouseButton;
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::ttf::Font;
-use std::io::{self, Write};
+use std::path::Path;
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
 
+mod cli;
+mod color;
+mod commands;
 mod config_loader;
-use config_loader::RegressivaConfig;
-
-
-// Função para renderizar texto no canvas
-fn render_text<'a>(
-    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+mod config_schema;
+mod config_validation;
+mod config_watcher;
+mod events;
+mod fonts;
+mod follower;
+mod hooks;
+mod i18n;
+mod http_api;
+mod logger;
+mod mic_mute;
+mod obs;
+mod osc;
+mod overlay;
+mod pacing;
+mod render;
+mod settings;
+mod sync;
+mod text_output;
+mod timer;
+mod timer_event;
+mod ws_server;
+use commands::{Command, RemoteRequest};
+use config_loader::{DebugConfig, ModoSincronia, RegressivaConfig};
+use render::RenderError;
+use timer::Countdown;
+
+
+// Desenha um botão preenchido com o texto centralizado
+fn render_button(
+    painter: &mut render::Painter,
     font: &Font,
+    rect: Rect,
+    fill_color: Color,
     text: &str,
-    color: Color,
-    text_rect: Rect,
+    text_color: Color,
+) -> Result<(), RenderError> {
+    painter.fill(rect, fill_color)?;
+    painter.text(font, text, text_color, rect)
+}
+
+// Renderiza o timer centralizado na janela principal
+fn render_timer_window(
+    window: &mut render::WindowCanvas,
+    font: &Font,
+    timer_text: &str,
+    timer_color: Color,
+    background_color: Color,
 ) {
-    if text.is_empty() {
-        return;
-    }
+    window.begin(background_color);
+    window.draw("timer", |painter| {
+        painter.text(font, timer_text, timer_color, Rect::new(0, 0, 1280, 720))
+    });
+    window.present();
+}
 
-    let surface = font.render(text).blended(color).unwrap();
-    let texture_creator = canvas.texture_creator();
-    let texture = texture_creator
-        .create_texture_from_surface(&surface)
-        .unwrap();
+// Carrega as fontes gigante, média, grande e pequena da janela de controle
+fn load_fonts<'a>(
+    ttf_context: &'a sdl2::ttf::Sdl2TtfContext,
+    config: &RegressivaConfig,
+) -> Result<(Font<'a, 'static>, Font<'a, 'static>, Font<'a, 'static>, Font<'a, 'static>), String> {
+    let source = fonts::resolve(&config.fontes);
+    log::info!(target: "fonts", "Fonte carregada: {}", source.describe());
+    Ok((
+        fonts::load(ttf_context, &source, config.fontes.get_fonte_gigante())?,
+        fonts::load(ttf_context, &source, config.fontes.get_fonte_media())?,
+        fonts::load(ttf_context, &source, config.fontes.get_fonte_grande())?,
+        fonts::load(ttf_context, &source, config.fontes.get_fonte_pequena())?,
+    ))
+}
 
-    let text_width = surface.width() as i32;
-    let text_height = surface.height() as i32;
+// Trata um clique na tela de ajustes; retorna a nova configuração quando ela é salva
+fn handle_settings_click(
+    settings: &mut Option<settings::SettingsEditor>,
+    x: i32,
+    y: i32,
+    config: &RegressivaConfig,
+    config_path: &Path,
+    texts: &i18n::Texts,
+) -> Option<RegressivaConfig> {
+    let editor = settings.as_mut()?;
+    match editor.click(x, y)? {
+        settings::SettingsAction::Cancel => {
+            *settings = None;
+            None
+        }
+        settings::SettingsAction::Save => {
+            let saved = editor.build(config).and_then(|new_config| {
+                editor
+                    .save(&new_config, config_path)
+                    .map(|_| new_config)
+                    .map_err(|e| texts.format("ajustes_erro_salvar", &[("erro", &e)]))
+            });
+            match saved {
+                Ok(new_config) => {
+                    log::info!(target: "config", "Ajustes salvos em {:?}", config_path);
+                    *settings = None;
+                    Some(new_config)
+                }
+                Err(e) => {
+                    log::warn!(target: "config", "Ajustes não salvos: {}", e);
+                    editor.message = Some(e);
+                    None
+                }
+            }
+        }
+    }
+}
 
-    let centered_rect = Rect::new(
-        text_rect.x() + (text_rect.width() as i32 - text_width) / 2,
-        text_rect.y() + (text_rect.height() as i32 - text_height) / 2,
-        text_width as u32,
-        text_height as u32,
-    );
+// Renderiza a tela de ajustes na janela de controle
+fn render_settings(
+    painter: &mut render::Painter,
+    font: &Font,
+    editor: &settings::SettingsEditor,
+    cursor_visible: bool,
+    config: &RegressivaConfig,
+    texts: &i18n::Texts,
+) -> Result<(), RenderError> {
+    for (index, field) in editor.fields().iter().enumerate() {
+        painter.text(font, &field.label, Color::WHITE, field.label_rect())?;
+
+        let focused = editor.focused() == Some(index);
+        let value = if focused && cursor_visible {
+            format!("{}|", field.value)
+        } else {
+            field.value.clone()
+        };
+        painter.fill(field.value_rect(), config.cores.get_button_color())?;
+        painter.text(font, &value, config.cores.get_text_color(), field.value_rect())?;
+        if focused {
+            painter.outline(field.value_rect(), Color::WHITE)?;
+        }
 
-    canvas.copy(&texture, None, Some(centered_rect)).unwrap();
-}
+        if let Some((rect, color)) = field.swatch() {
+            painter.fill(rect, color)?;
+            painter.outline(rect, Color::WHITE)?;
+        }
+    }
 
+    render_button(
+        painter,
+        font,
+        settings::SettingsEditor::save_button(),
+        config.cores.get_green_color(),
+        texts.get("ajustes_salvar"),
+        Color::WHITE,
+    )?;
+    render_button(
+        painter,
+        font,
+        settings::SettingsEditor::cancel_button(),
+        config.cores.get_red_color(),
+        texts.get("ajustes_cancelar"),
+        Color::WHITE,
+    )?;
+
+    if let Some(message) = &editor.message {
+        painter.text(
+            font,
+            message,
+            Color::RED,
+            settings::SettingsEditor::message_rect(),
+        )?;
+    }
+    Ok(())
+}
 
 // Função para lidar com cliques do mouse
 fn handle_mouse_click(
@@ -52,41 +188,40 @@ fn handle_mouse_click(
     y: i32,
     config: &RegressivaConfig,
     buttons: &ButtonPositions,
-    is_running: &mut bool,
-    countdown_duration: &mut Duration,
-    start_time: &mut Instant,
+    countdown: &mut Countdown,
 ) {
-    if buttons.start_button.contains_point((x, y)) && config.botoes.mostrar_botao_iniciar() {
-        *is_running = true;
-        *start_time = Instant::now();
+    let command = if buttons.start_button.contains_point((x, y)) && config.botoes.mostrar_botao_iniciar() {
+        Some(Command::Start)
     } else if buttons.pause_button.contains_point((x, y)) && config.botoes.mostrar_botao_pausar() {
-        *is_running = false;
+        Some(Command::Pause)
     } else if buttons.reset_button.contains_point((x, y)) && config.botoes.mostrar_botao_reset() {
-        *is_running = false;
-        *countdown_duration = Duration::new(config.tempo.get_tempo_reset(), 0);
+        Some(Command::Reset)
     } else if buttons.close_button.contains_point((x, y)) {
-
+        log::info!("Programa fechado pelo botão X");
         std::process::exit(0);
     } else {
-        let button_durations = [
-            (buttons.botao_1, config.tempo.get_tempo(1)),
-            (buttons.botao_2, config.tempo.get_tempo(2)),
-            (buttons.botao_3, config.tempo.get_tempo(3)),
-            (buttons.botao_4, config.tempo.get_tempo(4)),
-            (buttons.botao_5, config.tempo.get_tempo(5)),
-            (buttons.botao_6, config.tempo.get_tempo(6)),
-            (buttons.botao_7, config.tempo.get_tempo(7)),
-            (buttons.botao_8, config.tempo.get_tempo(8)),
-            (buttons.botao_9, config.tempo.get_tempo(9)),
+        let preset_buttons = [
+            buttons.botao_1,
+            buttons.botao_2,
+            buttons.botao_3,
+            buttons.botao_4,
+            buttons.botao_5,
+            buttons.botao_6,
+            buttons.botao_7,
+            buttons.botao_8,
+            buttons.botao_9,
         ];
 
-        for (button, duration) in button_durations.iter() {
-            if button.contains_point((x, y)) {
-                *countdown_duration = Duration::new(*duration, 0);
-                *is_running = false;
-                break;
-            }
-        }
+        preset_buttons
+            .iter()
+            .position(|button| button.contains_point((x, y)))
+            .map(|index| Command::Preset { index: index + 1 })
+    };
+
+    if let Some(command) = command {
+        log::info!("Botão: {:?}", command);
+        // Os comandos dos botões sempre são válidos
+        let _ = command.apply(countdown, config);
     }
 }
 
@@ -118,11 +253,11 @@ fn handle_backspace(input_text: &mut String) {
 }
 
 // Função para lidar com a tecla Enter
-fn handle_enter(input_text: &mut String, is_running: &mut bool, countdown_duration: &mut Duration) {
+fn handle_enter(input_text: &mut String, config: &RegressivaConfig, countdown: &mut Countdown) {
     if let Some((minutes, seconds)) = input_text.split_once(':') {
         if let (Ok(min), Ok(sec)) = (minutes.parse::<u64>(), seconds.parse::<u64>()) {
-            *is_running = false;
-            *countdown_duration = Duration::new(min * 60 + sec, 0);
+            log::info!("Tempo digitado: {}", input_text);
+            let _ = Command::Set { seconds: min * 60 + sec }.apply(countdown, config);
             input_text.clear();
         }
     }
@@ -145,144 +280,365 @@ struct ButtonPositions {
     botao_9: Rect,
 }
 
-fn boas_vindas() {
-    let greeting = r#"
-    Bem-vindo ao programa regressiva!
+// Mostra o guia do arquivo de configuração no idioma configurado
+fn boas_vindas(texts: &i18n::Texts) {
+    println!("{}", config_schema::guide(texts));
+}
 
-    Para personalizar as configurações, por favor, edite o arquivo de configuração. Aqui está um guia para ajudá-lo:
+// Textos no idioma do arquivo de configuração, se ele existir e for válido
+fn cli_texts(config_path: &Path) -> i18n::Texts {
+    let idioma = RegressivaConfig::read_config(config_path)
+        .map(|config| config.idioma)
+        .unwrap_or_default();
+    i18n::Texts::new(&idioma)
+}
 
-    [tempo] # Define os valores de tempo em segundos
-    - tempo1 a tempo9: Defina os valores de tempo (em segundos).
-    - tempo_reset: Defina o tempo (em segundos) para reiniciar.
+fn to_u32(value: f64) -> u32 {
+    value.round() as u32
+}
 
-    [tempo_texto] # Rótulos para os botões
-    - tempo_texto1 a tempo_texto9: Defina o texto de exibição para cada botão.
+// Escolhe o driver de vídeo configurado (ex.: dummy para testes sem monitor) antes de iniciar o SDL
+fn select_video_driver(config: &RegressivaConfig) {
+    let driver = config.renderizacao.get_driver_video();
+    if !driver.is_empty() {
+        log::info!("Driver de vídeo: {}", driver);
+        sdl2::hint::set("SDL_VIDEODRIVER", driver);
+    }
+}
 
-    [cores] # Configurações de cores
-    - cor_background: Defina a cor de fundo (RGB).
-    - cor_botoes: Defina a cor dos botões (RGB).
-    - cor_vermelho, cor_verde, cor_azul: Defina cores adicionais (RGB).
-    - cor_texto: Defina a cor do texto (RGB).
+// Abre apenas a janela do timer, exibindo o estado de uma instância remota
+fn run_follower(config: &RegressivaConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let follower = follower::Follower::start(config.seguidor.get_endereco());
 
-    [creditos] # Créditos do programa
-    - credito_texto: Defina o texto de crédito.
-    - mostrar_creditos: Mostrar ou ocultar créditos (verdadeiro/falso).
+    select_video_driver(config);
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+    let ttf_context = sdl2::ttf::init()?;
 
-    [botoes] # Controle de visibilidade dos botões
-    - botao_iniciar: Mostrar/Ocultar botão de iniciar.
-    - botao_pausar: Mostrar/Ocultar botão de pausar.
-    - botao_reset: Mostrar/Ocultar botão de reiniciar.
+    let mut canvas_timer = render::WindowCanvas::new(
+        &video_subsystem,
+        "Regressiva",
+        1280,
+        720,
+        config.renderizacao.get_janela_timer(),
+    )?;
+    log::info!("Seguindo o timer de {}", config.seguidor.get_endereco());
+
+    let font_source = fonts::resolve(&config.fontes);
+    log::info!(target: "fonts", "Fonte carregada: {}", font_source.describe());
+    let fonte_gigantic = fonts::load(&ttf_context, &font_source, config.fontes.get_fonte_gigante())?;
+    let custom_background_color = config.cores.get_background_color();
 
-    [debug] # Configurações de depuração
-    - mostrar_qps: Mostrar informações de QPS (quadros por segundo) (verdadeiro/falso).
-    - mostrar_timer: Mostrar informações do temporizador (verdadeiro/falso).
+    let mut event_pump = sdl_context.event_pump()?;
+    let mut redraw = true;
+    let mut last_frame = None;
+    let mut pending_event = None;
+    'running: loop {
+        for event in pending_event.take().into_iter().chain(event_pump.poll_iter()) {
+            redraw = true;
+            if let Event::Quit { .. } = event {
+                break 'running;
+            }
+        }
 
-    [fontes] # Configurações de fonte
-    - fonte: Defina o arquivo de fonte.
-    - fonte_gigante, fonte_grande, fonte_media, fonte_pequena: Defina os tamanhos das fontes.
+        // Sem notícias recentes da instância seguida, não exibe um tempo desatualizado
+        let frame = match follower.latest() {
+            Some(snapshot) => (snapshot.text.clone(), snapshot.color()),
+            None => ("--:--".to_string(), timer::TIMER_COLOR),
+        };
+        if redraw || canvas_timer.needs_redraw() || last_frame.as_ref() != Some(&frame) {
+            render_timer_window(
+                &mut canvas_timer,
+                &fonte_gigantic,
+                &frame.0,
+                frame.1,
+                custom_background_color,
+            );
+            last_frame = Some(frame);
+            redraw = false;
+        }
 
-    [boas_vindas]
-    - mostrar_boas_vindas: Mostrar/Ocultar mensagem de boas-vindas (verdadeiro/falso).
-    
-    Por favor, certifique-se de salvar suas alterações antes de iniciar o programa!
+        // O estado seguido chega pela rede, sem gerar eventos do SDL
+        pending_event = event_pump.wait_event_timeout(pacing::REMOTE_POLL_INTERVAL.as_millis() as u32);
+    }
+    Ok(())
+}
 
-    Para encerrar o programa, feche a janela do console.
-    
-    "#;
+// Evento do SDL que acorda o loop principal quando chega um comando remoto
+struct RemoteWakeUp;
+
+// Repassa os comandos das integrações ao loop principal, acordando-o da espera por eventos
+fn forward_remote_requests(
+    requests: mpsc::Receiver<RemoteRequest>,
+    wake_up: sdl2::event::EventSender,
+) -> mpsc::Receiver<RemoteRequest> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for request in requests {
+            if tx.send(request).is_err() {
+                break;
+            }
+            let _ = wake_up.push_custom_event(RemoteWakeUp);
+        }
+    });
+    rx
+}
 
-    println!("{}", greeting);
+/// Mostra um erro em uma janela, já que no Windows o console fica oculto
+fn show_error_window(title: &str, message: &str) {
+    log::error!("{}", message);
+    eprintln!("{}", message);
+    let _ = sdl2::messagebox::show_simple_message_box(
+        sdl2::messagebox::MessageBoxFlag::ERROR,
+        title,
+        message,
+        None,
+    );
 }
 
-fn to_u32(value: f64) -> u32 {
-    value.round() as u32
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let result = run();
+    if let Err(e) = &result {
+        log::error!("Programa encerrado por erro: {}", e);
+    }
+    result
 }
 
-fn update_countdown(
-    start_time: &mut Instant,
-    countdown_duration: &mut Duration,
-    is_running: &mut bool,
-) {
-    if *is_running {
-        let elapsed = start_time.elapsed();
-        if *countdown_duration > elapsed {
-            *countdown_duration -= elapsed;
-        } else {
-            *countdown_duration = Duration::new(0, 0);
-            *is_running = false;
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let args = match cli::CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
         }
+    };
+
+    // As ações de linha de comando imprimem no console de quem as chamou
+    if args.action != cli::Action::Run {
+        cli::attach_console();
     }
-    *start_time = Instant::now();
-}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+    match args.action {
+        cli::Action::Run => {}
+        cli::Action::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        cli::Action::Version => {
+            println!("regressiva {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        cli::Action::PrintDefaultConfig => {
+            print!("{}", RegressivaConfig::default().to_toml_string()?);
+            return Ok(());
+        }
+        cli::Action::HelpConfig => {
+            println!("{}", config_schema::guide(&cli_texts(&args.config_path)));
+            return Ok(());
+        }
+        cli::Action::PrintConfigSchema => {
+            let schema = config_schema::json_schema(&cli_texts(&args.config_path));
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+            return Ok(());
+        }
+        cli::Action::CheckConfig => {
+            return match RegressivaConfig::read_config(&args.config_path) {
+                Ok(_) => {
+                    println!("Configuração válida: {:?}", args.config_path);
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("Configuração inválida em {:?}: {}", args.config_path, e);
+                    std::process::exit(1);
+                }
+            };
+        }
+    }
 
-    let config = config_loader::RegressivaConfig::load_config()?;
+    // Registra desde o início com os valores padrão, até a configuração ser lida
+    logger::configure(&DebugConfig::default());
+    log::info!("regressiva {} iniciado", env!("CARGO_PKG_VERSION"));
+
+    // Um perfil ou um --config precisa existir; só o Config.toml padrão é criado automaticamente
+    if !args.config_path.exists() && args.config_path != Path::new(cli::DEFAULT_CONFIG_PATH) {
+        let (title, message) = match &args.profile {
+            Some(profile) => {
+                let available = cli::available_profiles();
+                (
+                    "Perfil não encontrado",
+                    format!(
+                        "Perfil {:?} não encontrado em {:?}. Perfis disponíveis: {}",
+                        profile,
+                        cli::PROFILES_DIR,
+                        if available.is_empty() {
+                            "nenhum".to_string()
+                        } else {
+                            available.join(", ")
+                        }
+                    ),
+                )
+            }
+            None => (
+                "Configuração não encontrada",
+                format!(
+                    "Arquivo de configuração {:?} não encontrado. Para criá-lo, use --print-default-config",
+                    args.config_path
+                ),
+            ),
+        };
+        show_error_window(title, &message);
+        return Err(message.into());
+    }
+
+    let mut config = match RegressivaConfig::load_config(&args.config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            show_error_window("Configuração inválida", &e.to_string());
+            return Err(e);
+        }
+    };
+    logger::configure(&config.debug);
+    log::info!(
+        target: "config",
+        "Configuração carregada de {:?}",
+        args.config_path
+    );
 
     
+    // Textos da interface no idioma configurado
+    let mut texts = i18n::Texts::new(&config.idioma);
+    let mut texts_language = config.idioma.clone();
+
     if config.boas_vindas.mostrar_boas_vindas() {
-        boas_vindas();
+        boas_vindas(&texts);
+    }
+
+    // No modo seguidor não há janela de controle nem integrações locais
+    if config.seguidor.habilitado() {
+        return run_follower(&config);
+    }
+
+    // Canal pelo qual as integrações remotas enviam comandos ao loop principal
+    let (remote_tx, remote_rx) = mpsc::channel::<RemoteRequest>();
+
+    // Inicia o servidor WebSocket de estado, se configurado
+    let mut state_publisher = if config.websocket.habilitado() {
+        let broadcaster = ws_server::start(&config.websocket, remote_tx.clone())?;
+        Some(ws_server::StatePublisher::new(broadcaster, config.websocket.get_intervalo()))
+    } else {
+        None
+    };
+
+    // Inicia a API HTTP de controle e status, se configurada
+    let http_status = if config.http.habilitado() {
+        let overlay = overlay::Overlay::from_config(&config);
+        Some(http_api::start(&config.http, overlay, remote_tx.clone())?)
+    } else {
+        None
+    };
+
+    // Inicia a entrada e a saída OSC, se configuradas
+    let mut osc_output = if config.osc.habilitado() {
+        osc::start_listener(&config.osc, remote_tx.clone())?;
+        let sender = osc::OscSender::new(config.osc.get_destinos().to_vec())?;
+        Some(osc::OscOutput::new(sender))
+    } else {
+        None
+    };
+
+    // Prepara o corte automático de microfones, se configurado
+    let mut mic_mute = if config.mixer.habilitado() {
+        Some(mic_mute::MicMute::new(&config)?)
+    } else {
+        None
+    };
+    let hold_open_key = Keycode::from_name(config.mixer.get_tecla_manter_aberto());
+
+    // Prepara os arquivos de texto para fontes de streaming, se configurados
+    let mut text_output = if config.arquivos_texto.habilitado() {
+        Some(text_output::TextOutput::new(&config.arquivos_texto))
+    } else {
+        None
+    };
+
+    // Inicia a sincronia entre instâncias, conforme o papel configurado
+    let mut sync_primary = None;
+    let mut sync_replica = None;
+    match config.sincronia.get_modo() {
+        ModoSincronia::Primario => sync_primary = Some(sync::SyncPrimary::start(&config.sincronia)?),
+        ModoSincronia::Replica => sync_replica = Some(sync::SyncReplica::start(&config.sincronia)),
+        ModoSincronia::Desligado => {}
     }
+    let promote_key = Keycode::from_name(config.sincronia.get_tecla_promover());
+    let mut promote_requested = false;
+
+    // Conecta ao OBS para executar ações nos eventos do timer, se configurado
+    let obs_client = if config.obs.habilitado() {
+        Some(obs::ObsClient::start(&config.obs))
+    } else {
+        None
+    };
+    let hooks = hooks::Hooks::new(&config.ganchos);
+    let mut event_detector = events::EventDetector::default();
+
     // Inicializa o SDL e seus subsistemas
+    select_video_driver(&config);
     let sdl_context = sdl2::init()?;
     let video_subsystem = sdl_context.video()?;
     let ttf_context = sdl2::ttf::init()?;
 
-    // Cria a janela do timer
-    let window_timer = video_subsystem
-        .window("Regressiva", 1280, 720)
-        .position_centered()
-        .borderless()
-        .build()?;
+    // Os comandos remotos acordam o loop principal, que fica esperando eventos quando nada muda
+    let event_subsystem = sdl_context.event()?;
+    event_subsystem.register_custom_event::<RemoteWakeUp>()?;
+    let remote_rx = forward_remote_requests(remote_rx, event_subsystem.event_sender());
 
-    let mut canvas_timer = window_timer.into_canvas().build()?;
-    canvas_timer.set_draw_color(Color::BLACK);
+    // Cria a janela do timer
+    let mut canvas_timer = render::WindowCanvas::new(
+        &video_subsystem,
+        "Regressiva",
+        1280,
+        720,
+        config.renderizacao.get_janela_timer(),
+    )?;
 
     // Cria a janela dos botões
-    let window_buttons = video_subsystem
-        .window("Controle", 1280, 720)
-        .position_centered()
-        .borderless()
-        .build()?;
-    let mut canvas_buttons = window_buttons.into_canvas().build()?;
-    let custom_background_color = config.cores.get_background_color();
-    let custom_botao_color = config.cores.get_button_color();
-    let custom_red_color = config.cores.get_red_color();
-    let custom_green_color = config.cores.get_green_color();
-    let custom_blue_color = config.cores.get_blue_color();
-    canvas_buttons.set_draw_color(custom_background_color);
-
-    // fallback para fonte padrao do windows
-    fn load_font_with_fallback<'a>(
-        ttf_context: &'a sdl2::ttf::Sdl2TtfContext,
-        font_path: &'a str,
-        font_size: u16,
-    ) -> Result<sdl2::ttf::Font<'a, 'a>, String> {
-        ttf_context.load_font(font_path, font_size).or_else(|_| {
-            // Fallback to a default Windows font
-            ttf_context.load_font("C:\\Windows\\Fonts\\arial.ttf", font_size)
-                .map_err(|e| format!("Failed to load fallback font: {:?}", e))
-        })
-    }
+    let mut canvas_buttons = render::WindowCanvas::new(
+        &video_subsystem,
+        texts.get("janela_controle"),
+        1280,
+        720,
+        config.renderizacao.get_janela_controle(),
+    )?;
 
     // Carrega as fontes
-let font_path = "./fonts/".to_owned() + &config.fontes.get_fonte();
-let fonte_gigantic = load_font_with_fallback(&ttf_context, &font_path, config.fontes.get_fonte_gigante())?;
-let font_medium = load_font_with_fallback(&ttf_context, &font_path, config.fontes.get_fonte_media())?;
-let fonte_large = load_font_with_fallback(&ttf_context, &font_path, config.fontes.get_fonte_grande())?;
-let font_small = load_font_with_fallback(&ttf_context, &font_path, config.fontes.get_fonte_pequena())?;
+    let (mut fonte_gigantic, mut font_medium, mut fonte_large, mut font_small) =
+        load_fonts(&ttf_context, &config)?;
 
+    // Observa o arquivo de configuração para aplicar alterações sem reiniciar
+    let mut config_watcher =
+        config_watcher::ConfigWatcher::new(&args.config_path, config_watcher::POLL_INTERVAL);
+    let mut config_rejected: Option<String> = None;
+
+    // Tela de ajustes aberta na janela de controle e a configuração salva por ela
+    let mut settings: Option<settings::SettingsEditor> = None;
+    let mut saved_config: Option<Box<RegressivaConfig>> = None;
 
     // Inicializa variáveis de controle
-    let mut start_time = Instant::now();
-    let mut countdown_duration = Duration::new(30, 0);
-    let mut is_running = false;
+    let mut countdown = Countdown::new(Duration::new(30, 0));
     let mut input_text = String::new();
-    let placeholder_text = "Clique para editar".to_string();
 
     // Variáveis para cálculo de FPS
     let mut last_fps_update = Instant::now();
     let mut frame_count = 0;
     let mut fps = 0.0;
+    let mut last_logged_text = String::new();
+
+    // Estado exibido no último quadro de cada janela; elas só são redesenhadas quando ele muda
+    let mut redraw = true;
+    let mut last_timer_frame = None;
+    let mut last_controls_frame = None;
+    let mut frame_scheduler = pacing::FrameScheduler::default();
+    let mut pending_event = None;
 
     // Define as posições dos botões
     let buttons = ButtonPositions {
@@ -375,6 +731,16 @@ let font_small = load_font_with_fallback(&ttf_context, &font_path, config.fontes
         to_u32(50.0 * 1.44),
     );
 
+    let hold_open_rect = Rect::new(
+        to_u32(50.0 * 3.2) as i32,
+        10,
+        to_u32(300.0 * 3.2),
+        40,
+    );
+
+    let sync_rect = Rect::new(1010, 10, 210, 40);
+    let config_rejected_rect = Rect::new(160, 620, 960, 36);
+
     let mut event_pump = sdl_context.event_pump()?;
 
     // Variáveis adicionadas antes do loop
@@ -388,45 +754,74 @@ let font_small = load_font_with_fallback(&ttf_context, &font_path, config.fontes
         let mouse_state = event_pump.mouse_state();
         let now = Instant::now();
 
-        // Processa eventos
-        for event in event_pump.poll_iter() {
+        // Processa eventos; qualquer evento (clique, tecla, janela exposta...) redesenha as janelas
+        for event in pending_event.take().into_iter().chain(event_pump.poll_iter()) {
+            redraw = true;
             match event {
                 Event::Quit { .. } => break 'running,
                 Event::MouseButtonDown {
-                    x, y, mouse_btn, ..
+                    x, y, mouse_btn: MouseButton::Left, ..
+                } if settings.is_some() => {
+                    saved_config =
+                        handle_settings_click(&mut settings, x, y, &config, &args.config_path, &texts)
+                            .map(Box::new);
+                }
+                Event::MouseButtonDown {
+                    x, y, mouse_btn: MouseButton::Left, ..
+                } if settings::SettingsEditor::open_button().contains_point((x, y)) => {
+                    settings = Some(settings::SettingsEditor::open(&config, &texts));
+                    is_input_focused = false;
+                }
+                Event::MouseButtonDown {
+                    x, y, mouse_btn: MouseButton::Left, ..
                 } => {
-                    if mouse_btn == MouseButton::Left {
-                        handle_mouse_click(
-                            x,
-                            y,
-                            &config,
-                            &buttons,
-                            &mut is_running,
-                            &mut countdown_duration,
-                            &mut start_time,
-                        );
-
-                        // Verifica se o clique foi dentro da área de entrada de texto
-                        if input_rect.contains_point((x, y)) {
-                            is_input_focused = true;
-                        } else {
-                            is_input_focused = false;
-                        }
-                    }
+                    handle_mouse_click(
+                        x,
+                        y,
+                        &config,
+                        &buttons,
+                        &mut countdown,
+                    );
+
+                    // Verifica se o clique foi dentro da área de entrada de texto
+                    is_input_focused = input_rect.contains_point((x, y));
                 }
-                Event::TextInput { text, .. } => {
-                    if is_input_focused {
-                        handle_text_input(text, &mut input_text, &mouse_state, input_rect);
+                Event::TextInput { text, .. } if settings.is_some() => {
+                    if let Some(editor) = settings.as_mut() {
+                        editor.type_text(&text);
                     }
                 }
                 Event::KeyDown {
                     keycode: Some(Keycode::Backspace),
                     ..
-                } => {
-                    if is_input_focused {
-                        handle_backspace(&mut input_text);
+                } if settings.is_some() => {
+                    if let Some(editor) = settings.as_mut() {
+                        editor.backspace();
+                    }
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Return | Keycode::KP_ENTER),
+                    ..
+                } if settings.is_some() => {
+                    if let Some(editor) = settings.as_mut() {
+                        editor.unfocus();
                     }
                 }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if settings.is_some() => {
+                    settings = None;
+                }
+                Event::TextInput { text, .. } if is_input_focused => {
+                    handle_text_input(text, &mut input_text, &mouse_state, input_rect);
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Backspace),
+                    ..
+                } if is_input_focused => {
+                    handle_backspace(&mut input_text);
+                }
                 Event::KeyDown {
                     keycode: Some(Keycode::Return),
                     ..
@@ -434,246 +829,408 @@ let font_small = load_font_with_fallback(&ttf_context, &font_path, config.fontes
                 | Event::KeyDown {
                     keycode: Some(Keycode::KP_ENTER),
                     ..
-                } => {
-                    if is_input_focused {
-                        handle_enter(&mut input_text, &mut is_running, &mut countdown_duration);
+                } if is_input_focused => {
+                    handle_enter(&mut input_text, &config, &mut countdown);
+                }
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } if Some(keycode) == hold_open_key => {
+                    if let Some(mic_mute) = mic_mute.as_mut() {
+                        mic_mute.toggle_hold_open();
+                        log::info!("Manter microfone aberto: {}", mic_mute.hold_open());
                     }
                 }
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } if Some(keycode) == promote_key => {
+                    promote_requested = sync_replica.is_some();
+                }
                 _ => {}
             }
         }
 
-        // Atualiza a visibilidade do cursor apenas se o campo de entrada estiver focado
-        if is_input_focused && now.duration_since(last_cursor_toggle) >= cursor_toggle_duration {
+        // Atualiza a visibilidade do cursor apenas se algum campo estiver em edição
+        let settings_focused = settings.as_ref().is_some_and(|editor| editor.focused().is_some());
+        if (is_input_focused || settings_focused) && now.duration_since(last_cursor_toggle) >= cursor_toggle_duration {
             cursor_visible = !cursor_visible;
             last_cursor_toggle = now;
         }
 
+        // Recarrega aparência, tempos e botões se o arquivo de configuração mudou ou foi salvo nos ajustes
+        let reload = match saved_config.take() {
+            Some(new_config) => Some(config_watcher::Reload::Applied(new_config)),
+            None => config_watcher.poll(),
+        };
+        match reload {
+            Some(config_watcher::Reload::Applied(new_config)) => {
+                if new_config.fontes == config.fontes {
+                    config.apply_live(*new_config);
+                    config_rejected = None;
+                } else {
+                    match load_fonts(&ttf_context, &new_config) {
+                        Ok(fonts) => {
+                            (fonte_gigantic, font_medium, fonte_large, font_small) = fonts;
+                            canvas_timer.clear_cache();
+                            canvas_buttons.clear_cache();
+                            config.apply_live(*new_config);
+                            config_rejected = None;
+                        }
+                        Err(e) => {
+                            log::warn!(
+                                target: "fonts",
+                                "Falha ao carregar as novas fontes: {}",
+                                e
+                            );
+                            config_rejected = Some(e);
+                        }
+                    }
+                }
+                redraw = true;
+                if config_rejected.is_none() {
+                    logger::configure(&config.debug);
+                    log::info!(
+                        target: "config",
+                        "Configuração recarregada de {:?}",
+                        args.config_path
+                    );
+                }
+            }
+            // Uma edição inválida é ignorada e a configuração atual continua valendo
+            Some(config_watcher::Reload::Rejected(e)) => {
+                log::warn!(
+                    target: "config",
+                    "Alteração da configuração rejeitada: {}",
+                    e
+                );
+                config_rejected = Some(e);
+                redraw = true;
+            }
+            None => {}
+        }
+        if texts_language != config.idioma {
+            texts = i18n::Texts::new(&config.idioma);
+            texts_language = config.idioma.clone();
+        }
+        let custom_background_color = config.cores.get_background_color();
+        let custom_botao_color = config.cores.get_button_color();
+        let custom_red_color = config.cores.get_red_color();
+        let custom_green_color = config.cores.get_green_color();
+        let custom_blue_color = config.cores.get_blue_color();
+
+        // Aplica os comandos recebidos das integrações remotas
+        while let Ok(request) = remote_rx.try_recv() {
+            let result = request.command.apply(&mut countdown, &config);
+            match &result {
+                Ok(()) => log::info!("Comando remoto: {:?}", request.command),
+                Err(e) => log::warn!("Comando remoto {:?} recusado: {}", request.command, e),
+            }
+            let _ = request.reply.send(result);
+        }
+
+        // Espelha o primário ou assume o seu lugar quando o link cai
+        if let Some(replica) = &sync_replica {
+            replica.apply(&mut countdown);
+            let link_lost = replica.link_lost() && config.sincronia.promover_automaticamente();
+            if link_lost || promote_requested {
+                log::warn!("Réplica promovida a primário (link perdido: {})", link_lost);
+                sync_replica = None;
+                // Se o endereço de escuta estiver ocupado, segue como instância isolada
+                sync_primary = sync::SyncPrimary::start(&config.sincronia).ok();
+                promote_requested = false;
+            }
+        }
+
         // Atualiza a contagem regressiva
-        update_countdown(&mut start_time, &mut countdown_duration, &mut is_running);
+        countdown.update();
+        if let Some(primary) = sync_primary.as_mut() {
+            primary.publish(&countdown);
+        }
+        let snapshot = countdown.snapshot(&config);
+
+        // Publica o estado para os clientes WebSocket
+        if let Some(publisher) = state_publisher.as_mut() {
+            publisher.publish(&snapshot);
+        }
+        if let Some(status) = &http_status {
+            status.update(&snapshot);
+        }
+        if let Some(output) = osc_output.as_mut() {
+            output.publish(&snapshot);
+        }
+        if let Some(mic_mute) = mic_mute.as_mut() {
+            mic_mute.update(&snapshot, now);
+        }
+        if let Some(output) = text_output.as_mut() {
+            output.publish(&snapshot);
+        }
 
-        // Calcula o tempo restante
-        let remaining_secs = countdown_duration.as_secs();
-        let minutes = remaining_secs / 60;
-        let seconds = remaining_secs % 60;
+        // Dispara as integrações ligadas aos eventos do timer
+        let timer_events = event_detector.detect(&countdown, &snapshot);
+        if let Some(obs_client) = &obs_client {
+            obs_client.notify(&timer_events);
+        }
+        hooks.run(&timer_events, &snapshot);
 
         // Define a cor do timer
-        let timer_color = if remaining_secs <= config.tempo.get_tempo_alerta_regressiva() && remaining_secs > 0 {
-            if seconds % 2 == 0 {
-                Color::RED
-            } else {
-                Color::WHITE
-            }
-        } else {
-            Color::WHITE
-        };
+        let timer_color = snapshot.color();
 
         // Renderiza o timer na janela principal
-        canvas_timer.set_draw_color(custom_background_color);
-        canvas_timer.clear();
-        let timer_text = format!("{:02}:{:02}", minutes, seconds);
-        let text_surface = fonte_gigantic.render(&timer_text).blended(timer_color)?;
-        let text_width = text_surface.width();
-        let text_height = text_surface.height();
-        let text_rect = Rect::new(
-            640 - text_width as i32 / 2,
-            360 - text_height as i32 / 2,
-            text_width,
-            text_height,
-        );
-        render_text(
-            &mut canvas_timer,
-            &fonte_gigantic,
-            &timer_text,
-            timer_color,
-            text_rect,
-        );
-        canvas_timer.present();
-
-        // Renderiza os botões e outros elementos na janela de controle
-        canvas_buttons.set_draw_color(custom_background_color);
-        canvas_buttons.clear();
-
-        // Renderiza o botão de iniciar
-        if config.botoes.mostrar_botao_iniciar() == true {
-            canvas_buttons.set_draw_color(custom_green_color);
-            canvas_buttons.fill_rect(buttons.start_button)?;
-            render_text(
-                &mut canvas_buttons,
-                &fonte_large,
-                "Iniciar",
-                Color::WHITE,
-                buttons.start_button,
+        let timer_text = &snapshot.text;
+        let timer_frame = (timer_text.clone(), timer_color, custom_background_color);
+        if redraw || canvas_timer.needs_redraw() || last_timer_frame.as_ref() != Some(&timer_frame) {
+            render_timer_window(
+                &mut canvas_timer,
+                &fonte_gigantic,
+                timer_text,
+                timer_color,
+                custom_background_color,
             );
+            last_timer_frame = Some(timer_frame);
         }
 
-        // Renderiza o botão de pausar
-        if config.botoes.mostrar_botao_pausar() == true {
-            canvas_buttons.set_draw_color(custom_blue_color);
-            canvas_buttons.fill_rect(buttons.pause_button)?;
-            render_text(
-                &mut canvas_buttons,
-                &fonte_large,
-                "Pausar",
-                Color::WHITE,
-                buttons.pause_button,
-            );
+        // Calcula o QPS (quadros desenhados por segundo na janela de controle)
+        if now.duration_since(last_fps_update).as_secs() >= 1 {
+            fps = frame_count as f64 / now.duration_since(last_fps_update).as_secs_f64();
+            frame_count = 0;
+            last_fps_update = now;
+            log::debug!(target: "qps", "QPS: {:.2}", fps);
         }
 
-        // Renderiza o botão de reset
-        if config.botoes.mostrar_botao_reset() {
-            canvas_buttons.set_draw_color(custom_red_color);
-            canvas_buttons.fill_rect(buttons.reset_button)?;
-            render_text(
-                &mut canvas_buttons,
-                &fonte_large,
-                "Reset",
-                Color::WHITE,
-                buttons.reset_button,
-            );
+        if *timer_text != last_logged_text {
+            log::debug!(target: "timer", "{}", timer_text);
+            last_logged_text.clone_from(timer_text);
         }
 
-        // Renderiza o botão de fechar
-        canvas_buttons.set_draw_color(Color::RGB(9, 61, 83));
-        canvas_buttons.fill_rect(buttons.close_button)?;
-        render_text(
-            &mut canvas_buttons,
-            &fonte_large,
-            "X",
-            Color::WHITE,
-            buttons.close_button,
+        // Mostra o papel da instância na sincronia
+        let sync_text = match (&sync_primary, &sync_replica) {
+            (Some(_), _) => texts.get("sincronia_primario"),
+            (_, Some(replica)) if replica.connected() => texts.get("sincronia_replica"),
+            (_, Some(_)) => texts.get("sincronia_sem_conexao"),
+            _ => "",
+        };
+        let hold_open = mic_mute.as_ref().is_some_and(|mic_mute| mic_mute.hold_open());
+        let fps_text = config.debug.depurar("qps").then(|| format!("QPS: {:.2}", fps));
+        let controls_frame = (
+            timer_text.clone(),
+            timer_color,
+            cursor_visible,
+            hold_open,
+            sync_text.to_string(),
+            fps_text.clone(),
         );
+        if redraw || canvas_buttons.needs_redraw() || last_controls_frame.as_ref() != Some(&controls_frame) {
+            last_controls_frame = Some(controls_frame);
+            frame_count += 1;
 
-        // Renderiza os botões de tempo
-        let button_texts = [
-            (buttons.botao_1, &config.tempo_texto.get_tempo_texto(1)),
-            (buttons.botao_2, &config.tempo_texto.get_tempo_texto(2)),
-            (buttons.botao_3, &config.tempo_texto.get_tempo_texto(3)),
-            (buttons.botao_4, &config.tempo_texto.get_tempo_texto(4)),
-            (buttons.botao_5, &config.tempo_texto.get_tempo_texto(5)),
-            (buttons.botao_6, &config.tempo_texto.get_tempo_texto(6)),
-            (buttons.botao_7, &config.tempo_texto.get_tempo_texto(7)),
-            (buttons.botao_8, &config.tempo_texto.get_tempo_texto(8)),
-            (buttons.botao_9, &config.tempo_texto.get_tempo_texto(9)),
-        ];
+            // Renderiza os botões e outros elementos na janela de controle; um elemento com falha é pulado
+            canvas_buttons.begin(custom_background_color);
 
-        for (button, text) in button_texts.iter() {
-            canvas_buttons.set_draw_color(custom_botao_color);
-            canvas_buttons.fill_rect(*button)?;
-            render_text(
-                &mut canvas_buttons,
-                &font_medium,
-                text,
-                config.cores.get_text_color(),
-                *button,
-            );
-        }
+            if let Some(editor) = &settings {
+                canvas_buttons.draw("ajustes", |painter| {
+                    render_settings(painter, &font_small, editor, cursor_visible, &config, &texts)
+                });
+            } else {
+                // Renderiza o botão de iniciar
+                if config.botoes.mostrar_botao_iniciar() {
+                    canvas_buttons.draw("botao_iniciar", |painter| {
+                        render_button(
+                            painter,
+                            &fonte_large,
+                            buttons.start_button,
+                            custom_green_color,
+                            texts.get("botao_iniciar"),
+                            Color::WHITE,
+                        )
+                    });
+                }
 
-        // Renderiza a área de entrada de texto com o cursor
-        if is_input_focused {
-            if cursor_visible {
-                if input_text.is_empty() {
-                    // Renderiza apenas o cursor
-                    render_text(
-                        &mut canvas_buttons,
-                        &font_medium,
-                        "|",
+                // Renderiza o botão de pausar
+                if config.botoes.mostrar_botao_pausar() {
+                    canvas_buttons.draw("botao_pausar", |painter| {
+                        render_button(
+                            painter,
+                            &fonte_large,
+                            buttons.pause_button,
+                            custom_blue_color,
+                            texts.get("botao_pausar"),
+                            Color::WHITE,
+                        )
+                    });
+                }
+
+                // Renderiza o botão de reset
+                if config.botoes.mostrar_botao_reset() {
+                    canvas_buttons.draw("botao_reset", |painter| {
+                        render_button(
+                            painter,
+                            &fonte_large,
+                            buttons.reset_button,
+                            custom_red_color,
+                            texts.get("botao_reset"),
+                            Color::WHITE,
+                        )
+                    });
+                }
+
+                // Renderiza o botão de fechar
+                canvas_buttons.draw("botao_fechar", |painter| {
+                    render_button(
+                        painter,
+                        &fonte_large,
+                        buttons.close_button,
+                        Color::RGB(9, 61, 83),
+                        "X",
                         Color::WHITE,
-                        input_rect,
-                    );
+                    )
+                });
+
+                // Renderiza os botões de tempo
+                let button_texts = [
+                    ("botao_1", buttons.botao_1, config.tempo_texto.get_tempo_texto(1)),
+                    ("botao_2", buttons.botao_2, config.tempo_texto.get_tempo_texto(2)),
+                    ("botao_3", buttons.botao_3, config.tempo_texto.get_tempo_texto(3)),
+                    ("botao_4", buttons.botao_4, config.tempo_texto.get_tempo_texto(4)),
+                    ("botao_5", buttons.botao_5, config.tempo_texto.get_tempo_texto(5)),
+                    ("botao_6", buttons.botao_6, config.tempo_texto.get_tempo_texto(6)),
+                    ("botao_7", buttons.botao_7, config.tempo_texto.get_tempo_texto(7)),
+                    ("botao_8", buttons.botao_8, config.tempo_texto.get_tempo_texto(8)),
+                    ("botao_9", buttons.botao_9, config.tempo_texto.get_tempo_texto(9)),
+                ];
+
+                for (element, button, text) in button_texts {
+                    canvas_buttons.draw(element, |painter| {
+                        render_button(
+                            painter,
+                            &font_medium,
+                            button,
+                            custom_botao_color,
+                            text,
+                            config.cores.get_text_color(),
+                        )
+                    });
+                }
+
+                // Renderiza a área de entrada de texto com o cursor, se ela estiver em edição
+                let (input_font, input_display, input_color) = if is_input_focused {
+                    let display_text = if cursor_visible {
+                        format!("{}|", input_text)
+                    } else {
+                        input_text.clone()
+                    };
+                    (&font_medium, display_text, Color::WHITE)
+                } else if input_text.is_empty() {
+                    // Mostra o placeholder se o campo não estiver focado e estiver vazio
+                    (&fonte_large, texts.get("campo_placeholder").to_string(), Color::GRAY)
                 } else {
-                    // Renderiza o texto com o cursor
-                    let display_text = format!("{}|", input_text);
-                    render_text(
-                        &mut canvas_buttons,
+                    (&font_small, input_text.clone(), Color::WHITE)
+                };
+                canvas_buttons.draw("campo_tempo", |painter| {
+                    painter.text(input_font, &input_display, input_color, input_rect)?;
+                    painter.outline(input_rect, Color::WHITE)
+                });
+
+                // Renderiza o timer pequeno na janela de controle
+                canvas_buttons.draw("timer", |painter| {
+                    painter.text(&fonte_large, timer_text, timer_color, small_timer_rect)
+                });
+
+                // Renderiza os créditos, se configurado
+                if config.creditos.mostrar_creditos() {
+                    canvas_buttons.draw("creditos", |painter| {
+                        painter.text(
+                            &font_small,
+                            config.creditos.get_credito_texto(),
+                            Color::WHITE,
+                            Rect::new(160, 660, 960, 72),
+                        )
+                    });
+                }
+
+                // Renderiza o botão que abre a tela de ajustes
+                canvas_buttons.draw("botao_ajustes", |painter| {
+                    render_button(
+                        painter,
                         &font_medium,
-                        &display_text,
+                        settings::SettingsEditor::open_button(),
+                        custom_botao_color,
+                        texts.get("botao_ajustes"),
+                        config.cores.get_text_color(),
+                    )
+                });
+            }
+
+            // Avisa o operador que o microfone não será cortado no zero
+            if hold_open {
+                canvas_buttons.draw("microfone_aberto", |painter| {
+                    render_button(
+                        painter,
+                        &font_small,
+                        hold_open_rect,
+                        custom_red_color,
+                        texts.get("microfone_aberto"),
                         Color::WHITE,
-                        input_rect,
-                    );
-                }
-            } else {
-                // Renderiza apenas o texto sem o cursor
-                render_text(
-                    &mut canvas_buttons,
-                    &font_medium,
-                    &input_text,
-                    Color::WHITE,
-                    input_rect,
-                );
+                    )
+                });
             }
-        } else if input_text.is_empty() {
-            // Renderiza o placeholder se o campo não estiver focado e estiver vazio
-            render_text(
-                &mut canvas_buttons,
-                &fonte_large,
-                &placeholder_text,
-                Color::GRAY,
-                input_rect,
-            );
-        } else {
-            // Renderiza o texto sem o cursor
-            render_text(
-                &mut canvas_buttons,
-                &font_small,
-                &input_text,
-                Color::WHITE,
-                input_rect,
-            );
-        }
-        canvas_buttons.set_draw_color(Color::WHITE);
-        canvas_buttons.draw_rect(input_rect)?;
 
-        // Renderiza o timer pequeno na janela de controle
-        render_text(
-            &mut canvas_buttons,
-            &fonte_large,
-            &timer_text,
-            timer_color,
-            small_timer_rect,
-        );
+            // Avisa o operador que a última edição do arquivo de configuração foi rejeitada
+            if let Some(e) = config_rejected.as_ref().filter(|_| settings.is_none()) {
+                // A primeira linha só repete o nome do arquivo
+                let problem = e.lines().nth(1).unwrap_or(e).trim_start_matches("  - ");
+                canvas_buttons.draw("alteracao_rejeitada", |painter| {
+                    painter.text(
+                        &font_small,
+                        &texts.format("alteracao_rejeitada", &[("motivo", problem)]),
+                        custom_red_color,
+                        config_rejected_rect,
+                    )
+                });
+            }
 
-        // Renderiza os créditos, se configurado
-        if config.creditos.mostrar_creditos() == true {
-            let tips_text = &config.creditos.get_credito_texto();
-            render_text(
-                &mut canvas_buttons,
-                &font_small,
-                tips_text,
-                Color::WHITE,
-                Rect::new(160, 660, 960, 72),
-                
-            );
-        }
+            // Mostra o papel da instância na sincronia
+            canvas_buttons.draw("sincronia", |painter| {
+                painter.text(&font_small, sync_text, Color::WHITE, sync_rect)
+            });
 
-        // Calcula e renderiza o FPS, se configurado
-        frame_count += 1;
-        if now.duration_since(last_fps_update).as_secs() >= 1 {
-            fps = frame_count as f64 / now.duration_since(last_fps_update).as_secs_f64();
-            frame_count = 0;
-            last_fps_update = now;
+            // Renderiza o QPS, se configurado
+            if let Some(fps_text) = &fps_text {
+                canvas_buttons.draw("qps", |painter| {
+                    painter.text(
+                        &font_medium,
+                        fps_text,
+                        Color::WHITE,
+                        Rect::new(10, 10, 100, 30),
+                    )
+                });
+            }
+            canvas_buttons.present();
         }
+        redraw = false;
 
-        if config.debug.mostrar_timer() == true {
-            print!("{}", timer_text); // Using println! for automatic newline and flush
+        // Acorda na próxima virada de segundo, no piscar do cursor ou no próximo envio das
+        // integrações; sem nada agendado, só o arquivo de configuração é verificado
+        if let Some(delay) = countdown.until_next_second() {
+            frame_scheduler.wake_at(countdown.start_time + delay);
         }
-
-        if config.debug.mostrar_qps() == true {
-            let fps_text = format!("QPS: {:.2}", fps);
-            print!(" | {}", fps_text); // Using println! for automatic newline and flush
-            render_text(
-                &mut canvas_buttons,
-                &font_medium,
-                &fps_text,
-                Color::WHITE,
-                Rect::new(10, 10, 100, 30),
-            );
+        if is_input_focused || settings_focused {
+            frame_scheduler.wake_at(last_cursor_toggle + cursor_toggle_duration);
         }
-        io::stdout().flush().unwrap();
-        canvas_buttons.present();
-
-        // Limita a taxa de atualização
-        std::thread::sleep(Duration::from_millis(32));
+        if let Some(publisher) = &state_publisher {
+            frame_scheduler.wake_at(publisher.next_publish());
+        }
+        if let Some(primary) = &sync_primary {
+            frame_scheduler.wake_at(primary.next_publish());
+        }
+        if sync_replica.is_some() {
+            frame_scheduler.wake_in(now, pacing::REMOTE_POLL_INTERVAL);
+        }
+        if let Some(deadline) = mic_mute.as_ref().and_then(|mic_mute| mic_mute.pending_deadline()) {
+            frame_scheduler.wake_at(deadline);
+        }
+        let timeout = frame_scheduler.take_timeout(Instant::now(), config_watcher::POLL_INTERVAL);
+        pending_event = event_pump.wait_event_timeout(timeout);
     }
     Ok(())
 }
@@ -0,0 +1,262 @@
+use crate::config_loader::{MensagemOscConfig, OradorConfig, RegressivaConfig, ValorOsc};
+use crate::osc::{OscArg, OscMessage, OscSender};
+use crate::timer::{AlertStage, TimerSnapshot, TimerStatus};
+use std::io;
+use std::time::{Duration, Instant};
+
+// Corte agendado para o fim da carência
+struct PendingMute {
+    deadline: Instant,
+    messages: Vec<OscMessage>,
+}
+
+/// Corta o microfone do orador na mesa de som quando o tempo dele zera
+pub struct MicMute {
+    sender: OscSender,
+    oradores: Vec<OradorConfig>,
+    mensagens: Vec<MensagemOscConfig>,
+    carencia: Duration,
+    pending: Option<PendingMute>,
+    hold_open: bool,
+    last_status: Option<TimerStatus>,
+}
+
+impl MicMute {
+    pub fn new(config: &RegressivaConfig) -> io::Result<Self> {
+        let sender = OscSender::new(vec![config.mixer.get_destino().to_string()])?;
+        Ok(MicMute::with_sender(config, sender))
+    }
+
+    fn with_sender(config: &RegressivaConfig, sender: OscSender) -> Self {
+        MicMute {
+            sender,
+            oradores: config.oradores.clone(),
+            mensagens: config.mixer.get_mensagens().to_vec(),
+            carencia: config.mixer.get_carencia(),
+            pending: None,
+            hold_open: false,
+            last_status: None,
+        }
+    }
+
+    /// Alterna a opção de manter o microfone aberto, cancelando o corte pendente
+    pub fn toggle_hold_open(&mut self) {
+        self.hold_open = !self.hold_open;
+        if self.hold_open {
+            self.pending = None;
+        }
+    }
+
+    /// Retorna se o microfone está sendo mantido aberto pelo operador
+    pub fn hold_open(&self) -> bool {
+        self.hold_open
+    }
+
+    /// Retorna quando termina a carência do corte agendado, se houver
+    pub fn pending_deadline(&self) -> Option<Instant> {
+        self.pending.as_ref().map(|pending| pending.deadline)
+    }
+
+    /// Agenda o corte quando o timer zera e envia as mensagens ao fim da carência
+    pub fn update(&mut self, snapshot: &TimerSnapshot, now: Instant) {
+        let reached_zero = self.last_status == Some(TimerStatus::Running)
+            && snapshot.alert_stage == AlertStage::Zero;
+        self.last_status = Some(snapshot.status);
+
+        // Um novo tempo ou o reinício da contagem cancela o corte
+        if snapshot.alert_stage != AlertStage::Zero {
+            self.pending = None;
+        }
+
+        if reached_zero && !self.hold_open {
+            let messages = snapshot
+                .speaker
+                .as_deref()
+                .map(|speaker| self.mute_messages(speaker))
+                .unwrap_or_default();
+            if !messages.is_empty() {
+                self.pending = Some(PendingMute {
+                    deadline: now + self.carencia,
+                    messages,
+                });
+            }
+        }
+
+        if self.pending.as_ref().is_some_and(|p| now >= p.deadline) {
+            if let Some(pending) = self.pending.take() {
+                for message in &pending.messages {
+                    self.sender.send(message);
+                }
+            }
+        }
+    }
+
+    // Monta as mensagens de corte do orador, com o canal no formato de dois dígitos
+    fn mute_messages(&self, speaker: &str) -> Vec<OscMessage> {
+        let Some(orador) = self.oradores.iter().find(|o| o.get_nome() == speaker) else {
+            return Vec::new();
+        };
+
+        let mensagens = if orador.get_mensagens().is_empty() {
+            &self.mensagens
+        } else {
+            orador.get_mensagens()
+        };
+
+        let canal = format!("{:02}", orador.get_canal());
+        mensagens
+            .iter()
+            .map(|mensagem| {
+                let args = mensagem
+                    .get_argumentos()
+                    .iter()
+                    .map(|valor| match valor {
+                        ValorOsc::Inteiro(value) => OscArg::Int(*value),
+                        ValorOsc::Decimal(value) => OscArg::Float(*value),
+                        ValorOsc::Texto(value) => OscArg::Str(value.clone()),
+                    })
+                    .collect();
+                OscMessage::new(&mensagem.get_endereco().replace("{canal}", &canal), args)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::Countdown;
+    use std::net::UdpSocket;
+
+    const CONFIG: &str = r#"
+        [mixer]
+        habilitado = true
+        destino = "127.0.0.1:0"
+        carencia_ms = 500
+        tecla_manter_aberto = "F2"
+        mensagens = [{ endereco = "/ch/{canal}/mix/on", argumentos = [0] }]
+
+        [[oradores]]
+        nome = "Ana"
+        canal = 5
+
+        [[oradores]]
+        nome = "Bruno"
+        canal = 12
+        mensagens = [{ endereco = "/ch/{canal}/mix/fader", argumentos = [0.0] }]
+    "#;
+
+    // Mesa de som simulada que recebe as mensagens OSC em loopback
+    fn stand_in_mixer() -> (UdpSocket, OscSender) {
+        let mixer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        mixer.set_nonblocking(true).unwrap();
+        let sender = OscSender::new(vec![mixer.local_addr().unwrap().to_string()]).unwrap();
+        (mixer, sender)
+    }
+
+    fn received(mixer: &UdpSocket) -> Vec<OscMessage> {
+        // Dá tempo para os pacotes chegarem pelo loopback
+        std::thread::sleep(Duration::from_millis(50));
+        let mut buffer = [0; 1536];
+        let mut messages = Vec::new();
+        while let Ok((size, _)) = mixer.recv_from(&mut buffer) {
+            messages.push(OscMessage::decode(&buffer[..size]).unwrap());
+        }
+        messages
+    }
+
+    fn config() -> RegressivaConfig {
+        #[derive(serde_derive::Deserialize)]
+        struct Extra {
+            mixer: crate::config_loader::MixerConfig,
+            oradores: Vec<OradorConfig>,
+        }
+
+        let extra: Extra = toml::from_str(CONFIG).unwrap();
+        RegressivaConfig {
+            mixer: extra.mixer,
+            oradores: extra.oradores,
+            ..RegressivaConfig::default()
+        }
+    }
+
+    // Simula o timer do orador chegando a zero
+    fn run_to_zero(mic_mute: &mut MicMute, speaker: &str, config: &RegressivaConfig, now: Instant) {
+        let mut countdown = Countdown::new(Duration::new(1, 0));
+        countdown.speaker = Some(speaker.to_string());
+        countdown.is_running = true;
+        mic_mute.update(&countdown.snapshot(config), now);
+        countdown.duration = Duration::ZERO;
+        countdown.is_running = false;
+        mic_mute.update(&countdown.snapshot(config), now);
+    }
+
+    #[test]
+    fn mutes_speaker_channel_after_grace_period() {
+        let config = config();
+        let (mixer, sender) = stand_in_mixer();
+        let mut mic_mute = MicMute::with_sender(&config, sender);
+        let start = Instant::now();
+
+        run_to_zero(&mut mic_mute, "Ana", &config, start);
+        assert!(received(&mixer).is_empty());
+
+        let zero = Countdown::new(Duration::ZERO).snapshot(&config);
+        mic_mute.update(&zero, start + Duration::from_millis(499));
+        assert!(received(&mixer).is_empty());
+
+        mic_mute.update(&zero, start + Duration::from_millis(500));
+        assert_eq!(
+            received(&mixer),
+            vec![OscMessage::new("/ch/05/mix/on", vec![OscArg::Int(0)])]
+        );
+
+        // O corte é enviado uma única vez
+        mic_mute.update(&zero, start + Duration::from_secs(2));
+        assert!(received(&mixer).is_empty());
+    }
+
+    #[test]
+    fn speaker_messages_override_the_defaults() {
+        let config = config();
+        let (mixer, sender) = stand_in_mixer();
+        let mut mic_mute = MicMute::with_sender(&config, sender);
+        let start = Instant::now();
+
+        run_to_zero(&mut mic_mute, "Bruno", &config, start);
+        let zero = Countdown::new(Duration::ZERO).snapshot(&config);
+        mic_mute.update(&zero, start + Duration::from_secs(1));
+        assert_eq!(
+            received(&mixer),
+            vec![OscMessage::new(
+                "/ch/12/mix/fader",
+                vec![OscArg::Float(0.0)]
+            )]
+        );
+    }
+
+    #[test]
+    fn hold_open_and_new_time_cancel_the_mute() {
+        let config = config();
+        let (mixer, sender) = stand_in_mixer();
+        let mut mic_mute = MicMute::with_sender(&config, sender);
+        let start = Instant::now();
+
+        run_to_zero(&mut mic_mute, "Ana", &config, start);
+        mic_mute.toggle_hold_open();
+        let zero = Countdown::new(Duration::ZERO).snapshot(&config);
+        mic_mute.update(&zero, start + Duration::from_secs(1));
+        assert!(received(&mixer).is_empty());
+
+        mic_mute.toggle_hold_open();
+        run_to_zero(&mut mic_mute, "Ana", &config, start);
+        let new_time = Countdown::new(Duration::new(30, 0)).snapshot(&config);
+        mic_mute.update(&new_time, start + Duration::from_secs(1));
+        assert!(received(&mixer).is_empty());
+
+        // Oradores sem canal configurado não geram mensagens
+        run_to_zero(&mut mic_mute, "Carla", &config, start);
+        mic_mute.update(&zero, start + Duration::from_secs(1));
+        assert!(received(&mixer).is_empty());
+    }
+}
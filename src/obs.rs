@@ -0,0 +1,271 @@
+use crate::config_loader::{AcaoObsConfig, ObsConfig};
+use crate::timer_event::TimerEvent;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+/// Versão do protocolo RPC do obs-websocket 5
+const RPC_VERSION: u64 = 1;
+
+// Códigos de operação do obs-websocket 5
+const OP_HELLO: u64 = 0;
+const OP_IDENTIFY: u64 = 1;
+const OP_IDENTIFIED: u64 = 2;
+const OP_REQUEST: u64 = 6;
+
+type ObsSocket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+/// Cliente do obs-websocket 5 que executa requisições nos eventos do timer
+pub struct ObsClient {
+    events: Sender<TimerEvent>,
+}
+
+impl ObsClient {
+    /// Inicia a thread de conexão com o OBS
+    pub fn start(config: &ObsConfig) -> Self {
+        let (events, receiver) = mpsc::channel();
+        let url = format!("ws://{}", config.get_endereco());
+        let senha = config.get_senha().to_string();
+        let acoes = config.get_acoes().to_vec();
+        thread::spawn(move || run(&url, &senha, &acoes, receiver));
+        ObsClient { events }
+    }
+
+    /// Repassa os eventos ocorridos para a thread do OBS
+    pub fn notify(&self, events: &[TimerEvent]) {
+        for event in events {
+            let _ = self.events.send(*event);
+        }
+    }
+}
+
+// Conecta sob demanda e executa as ações de cada evento recebido
+fn run(url: &str, senha: &str, acoes: &[AcaoObsConfig], events: Receiver<TimerEvent>) {
+    let mut socket: Option<ObsSocket> = None;
+    let mut next_request_id = 0u64;
+
+    for event in events {
+        let requests: Vec<&AcaoObsConfig> = acoes
+            .iter()
+            .filter(|acao| acao.get_evento() == event)
+            .collect();
+        if requests.is_empty() {
+            continue;
+        }
+
+        // Se a conexão tiver caído desde o último evento, reconecta e tenta mais uma vez; só as
+        // requisições ainda não enviadas são repetidas, para não alternar gravação duas vezes
+        let mut pending = requests.as_slice();
+        for _ in 0..2 {
+            if socket.is_none() {
+                socket = connect(url, senha)
+                    .map_err(|e| log::warn!("Falha ao conectar ao OBS em {}: {}", url, e))
+                    .ok();
+            }
+            let Some(active) = socket.as_mut() else {
+                break;
+            };
+
+            while let Some((acao, rest)) = pending.split_first() {
+                next_request_id += 1;
+                let request = json!({
+                    "op": OP_REQUEST,
+                    "d": {
+                        "requestType": acao.get_tipo(),
+                        "requestId": format!("regressiva-{}", next_request_id),
+                        "requestData": acao.get_dados(),
+                    }
+                });
+                if active.send(Message::Text(request.to_string())).is_err() {
+                    break;
+                }
+                pending = rest;
+            }
+
+            if pending.is_empty() {
+                // As requisições já foram entregues; uma falha aqui só derruba a conexão
+                if !drain(active) {
+                    socket = None;
+                }
+                break;
+            }
+            socket = None;
+        }
+        if !pending.is_empty() {
+            log::warn!(
+                "{} requisição(ões) do OBS para o evento {} não foram enviadas",
+                pending.len(),
+                event.label()
+            );
+        }
+    }
+}
+
+// Faz o handshake Hello/Identify, com autenticação quando o OBS exigir
+fn connect(url: &str, senha: &str) -> Result<ObsSocket, String> {
+    let (mut socket, _) = tungstenite::connect(url).map_err(|e| e.to_string())?;
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    }
+
+    let hello = read_json(&mut socket)?;
+    if hello["op"] != OP_HELLO {
+        return Err("o OBS não enviou Hello".to_string());
+    }
+
+    let mut identify = json!({ "rpcVersion": RPC_VERSION, "eventSubscriptions": 0 });
+    let auth = &hello["d"]["authentication"];
+    if let (Some(challenge), Some(salt)) = (auth["challenge"].as_str(), auth["salt"].as_str()) {
+        identify["authentication"] = json!(authentication(senha, salt, challenge));
+    }
+
+    socket
+        .send(Message::Text(
+            json!({ "op": OP_IDENTIFY, "d": identify }).to_string(),
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let identified = read_json(&mut socket)?;
+    if identified["op"] != OP_IDENTIFIED {
+        return Err("o OBS recusou a identificação".to_string());
+    }
+
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        let _ = stream.set_read_timeout(Some(Duration::from_millis(200)));
+    }
+    Ok(socket)
+}
+
+/// Calcula a resposta ao desafio de autenticação do obs-websocket 5
+pub fn authentication(senha: &str, salt: &str, challenge: &str) -> String {
+    let secret = BASE64.encode(Sha256::digest(format!("{}{}", senha, salt)));
+    BASE64.encode(Sha256::digest(format!("{}{}", secret, challenge)))
+}
+
+fn read_json(socket: &mut ObsSocket) -> Result<Value, String> {
+    loop {
+        match socket.read().map_err(|e| e.to_string())? {
+            Message::Text(text) => return serde_json::from_str(&text).map_err(|e| e.to_string()),
+            Message::Close(_) => return Err("o OBS fechou a conexão".to_string()),
+            _ => {}
+        }
+    }
+}
+
+// Descarta as respostas pendentes; retorna falso se a conexão caiu
+fn drain(socket: &mut ObsSocket) -> bool {
+    loop {
+        match socket.read() {
+            Ok(Message::Close(_)) => return false,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                return true
+            }
+            Err(_) => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    const CONFIG: &str = r#"
+        habilitado = true
+        endereco = "127.0.0.1:0"
+        senha = "segredo"
+
+        [[acoes]]
+        evento = "zero"
+        tipo = "SetCurrentProgramScene"
+        dados = { sceneName = "Tempo Esgotado" }
+
+        [[acoes]]
+        evento = "zero"
+        tipo = "TriggerMediaInputAction"
+        dados = { inputName = "Sirene", mediaAction = "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_RESTART" }
+
+        [[acoes]]
+        evento = "start"
+        tipo = "SetCurrentProgramScene"
+        dados = { sceneName = "Debate" }
+    "#;
+
+    // OBS simulado: faz o handshake com autenticação e devolve as requisições recebidas
+    fn stand_in_obs(listener: TcpListener, requests: Sender<Value>) {
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let hello = json!({
+                "op": OP_HELLO,
+                "d": {
+                    "obsWebSocketVersion": "5.0.0",
+                    "rpcVersion": 1,
+                    "authentication": { "challenge": "desafio", "salt": "sal" }
+                }
+            });
+            socket.send(Message::Text(hello.to_string())).unwrap();
+
+            while let Ok(message) = socket.read() {
+                let Message::Text(text) = message else {
+                    continue;
+                };
+                let value: Value = serde_json::from_str(&text).unwrap();
+                if value["op"] == OP_IDENTIFY {
+                    let expected = authentication("segredo", "sal", "desafio");
+                    assert_eq!(value["d"]["authentication"], expected);
+                    let identified =
+                        json!({ "op": OP_IDENTIFIED, "d": { "negotiatedRpcVersion": 1 } });
+                    socket.send(Message::Text(identified.to_string())).unwrap();
+                } else {
+                    let id = value["d"]["requestId"].clone();
+                    let response = json!({
+                        "op": 7,
+                        "d": { "requestId": id, "requestStatus": { "result": true, "code": 100 } }
+                    });
+                    socket.send(Message::Text(response.to_string())).unwrap();
+                    requests.send(value).unwrap();
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn sends_configured_requests_on_timer_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, requests) = mpsc::channel();
+        stand_in_obs(listener, tx);
+
+        let config: ObsConfig =
+            toml::from_str(&CONFIG.replace("127.0.0.1:0", &addr.to_string())).unwrap();
+        let client = ObsClient::start(&config);
+        client.notify(&[TimerEvent::Threshold, TimerEvent::Zero]);
+
+        let timeout = Duration::from_secs(5);
+        let first = requests.recv_timeout(timeout).unwrap();
+        assert_eq!(first["op"], OP_REQUEST);
+        assert_eq!(first["d"]["requestType"], "SetCurrentProgramScene");
+        assert_eq!(first["d"]["requestData"]["sceneName"], "Tempo Esgotado");
+
+        let second = requests.recv_timeout(timeout).unwrap();
+        assert_eq!(second["d"]["requestType"], "TriggerMediaInputAction");
+        assert_eq!(second["d"]["requestData"]["inputName"], "Sirene");
+
+        client.notify(&[TimerEvent::Start]);
+        let third = requests.recv_timeout(timeout).unwrap();
+        assert_eq!(third["d"]["requestData"]["sceneName"], "Debate");
+        assert!(requests.recv_timeout(Duration::from_millis(300)).is_err());
+    }
+}
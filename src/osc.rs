@@ -0,0 +1,375 @@
+use crate::commands::{self, Command, RemoteRequest};
+use crate::config_loader::OscConfig;
+use crate::timer::TimerSnapshot;
+use std::io;
+use std::net::UdpSocket;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+/// Prefixo de todos os endereços OSC do programa
+const PREFIX: &str = "/regressiva";
+
+/// Argumento de uma mensagem OSC
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    Str(String),
+}
+
+impl OscArg {
+    /// Interpreta o argumento como número inteiro
+    fn as_i64(&self) -> Option<i64> {
+        match self {
+            OscArg::Int(value) => Some(i64::from(*value)),
+            OscArg::Float(value) => Some(value.round() as i64),
+            OscArg::Str(value) => value.trim().parse().ok(),
+        }
+    }
+
+    /// Interpreta o argumento como texto
+    fn as_string(&self) -> String {
+        match self {
+            OscArg::Int(value) => value.to_string(),
+            OscArg::Float(value) => value.to_string(),
+            OscArg::Str(value) => value.clone(),
+        }
+    }
+}
+
+/// Mensagem OSC com endereço e argumentos
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: &str, args: Vec<OscArg>) -> Self {
+        OscMessage {
+            address: address.to_string(),
+            args,
+        }
+    }
+
+    /// Codifica a mensagem no formato binário do OSC 1.0
+    pub fn encode(&self) -> Vec<u8> {
+        let mut packet = Vec::new();
+        push_padded_str(&mut packet, &self.address);
+
+        let mut type_tags = String::from(",");
+        for arg in &self.args {
+            type_tags.push(match arg {
+                OscArg::Int(_) => 'i',
+                OscArg::Float(_) => 'f',
+                OscArg::Str(_) => 's',
+            });
+        }
+        push_padded_str(&mut packet, &type_tags);
+
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(value) => packet.extend_from_slice(&value.to_be_bytes()),
+                OscArg::Float(value) => packet.extend_from_slice(&value.to_be_bytes()),
+                OscArg::Str(value) => push_padded_str(&mut packet, value),
+            }
+        }
+
+        packet
+    }
+
+    /// Decodifica uma mensagem OSC; pacotes `#bundle` não são suportados
+    pub fn decode(packet: &[u8]) -> Result<OscMessage, String> {
+        let mut offset = 0;
+        let address = read_padded_str(packet, &mut offset)?;
+        if !address.starts_with('/') {
+            return Err(format!("endereço OSC inválido: {}", address));
+        }
+
+        // Mensagens sem type tags são aceitas como mensagens sem argumentos
+        if offset >= packet.len() {
+            return Ok(OscMessage::new(&address, Vec::new()));
+        }
+
+        let type_tags = read_padded_str(packet, &mut offset)?;
+        let Some(type_tags) = type_tags.strip_prefix(',') else {
+            return Err("type tags OSC inválidas".to_string());
+        };
+
+        let mut args = Vec::new();
+        for tag in type_tags.chars() {
+            let arg = match tag {
+                'i' => OscArg::Int(i32::from_be_bytes(read_word(packet, &mut offset)?)),
+                'f' => OscArg::Float(f32::from_be_bytes(read_word(packet, &mut offset)?)),
+                's' => OscArg::Str(read_padded_str(packet, &mut offset)?),
+                other => return Err(format!("tipo OSC não suportado: {}", other)),
+            };
+            args.push(arg);
+        }
+
+        Ok(OscMessage { address, args })
+    }
+}
+
+fn push_padded_str(packet: &mut Vec<u8>, value: &str) {
+    packet.extend_from_slice(value.as_bytes());
+    // Strings OSC terminam com ao menos um nulo e são alinhadas em 4 bytes
+    let padding = 4 - value.len() % 4;
+    packet.extend(std::iter::repeat_n(0, padding));
+}
+
+fn read_padded_str(packet: &[u8], offset: &mut usize) -> Result<String, String> {
+    let rest = packet.get(*offset..).unwrap_or_default();
+    let end = rest
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or_else(|| "string OSC sem terminador".to_string())?;
+    let value = String::from_utf8_lossy(&rest[..end]).into_owned();
+    *offset += (end / 4 + 1) * 4;
+    Ok(value)
+}
+
+fn read_word(packet: &[u8], offset: &mut usize) -> Result<[u8; 4], String> {
+    let word = packet
+        .get(*offset..*offset + 4)
+        .ok_or_else(|| "argumento OSC incompleto".to_string())?;
+    *offset += 4;
+    Ok([word[0], word[1], word[2], word[3]])
+}
+
+/// Converte uma mensagem OSC recebida no comando correspondente
+pub fn to_command(message: &OscMessage) -> Result<Command, String> {
+    let Some(action) = message.address.strip_prefix(PREFIX) else {
+        return Err(format!("endereço desconhecido: {}", message.address));
+    };
+
+    let first_number = || {
+        message
+            .args
+            .first()
+            .and_then(OscArg::as_i64)
+            .ok_or_else(|| format!("{} requer um argumento numérico", message.address))
+    };
+
+    match action {
+        "/start" => Ok(Command::Start),
+        "/pause" => Ok(Command::Pause),
+        "/reset" => Ok(Command::Reset),
+        "/set" => {
+            let seconds = first_number()?;
+            u64::try_from(seconds)
+                .map(|seconds| Command::Set { seconds })
+                .map_err(|_| "o tempo não pode ser negativo".to_string())
+        }
+        "/preset" => {
+            let index = first_number()?;
+            usize::try_from(index)
+                .map(|index| Command::Preset { index })
+                .map_err(|_| format!("preset inválido: {}", index))
+        }
+        "/nudge" => first_number().map(|seconds| Command::Nudge { seconds }),
+        "/next_segment" => Ok(Command::NextSegment),
+        "/speaker" => Ok(Command::Speaker {
+            name: message
+                .args
+                .first()
+                .map(OscArg::as_string)
+                .unwrap_or_default(),
+        }),
+        _ => Err(format!("endereço desconhecido: {}", message.address)),
+    }
+}
+
+/// Inicia o receptor OSC em uma thread separada
+pub fn start_listener(config: &OscConfig, commands: Sender<RemoteRequest>) -> io::Result<()> {
+    let socket = UdpSocket::bind(config.get_endereco())?;
+    listen(socket, commands);
+    Ok(())
+}
+
+/// Recebe mensagens OSC do socket e as envia como comandos ao loop principal
+pub fn listen(socket: UdpSocket, commands: Sender<RemoteRequest>) {
+    thread::spawn(move || {
+        let mut buffer = [0; 1536];
+        while let Ok((size, _)) = socket.recv_from(&mut buffer) {
+            // OSC não tem resposta; mensagens inválidas são descartadas
+            if let Ok(command) = OscMessage::decode(&buffer[..size]).and_then(|m| to_command(&m)) {
+                let _ = commands::send_command(&commands, command);
+            }
+        }
+    });
+}
+
+/// Envia mensagens OSC para uma lista de destinos
+pub struct OscSender {
+    socket: UdpSocket,
+    targets: Vec<String>,
+}
+
+impl OscSender {
+    pub fn new(targets: Vec<String>) -> io::Result<Self> {
+        Ok(OscSender {
+            socket: UdpSocket::bind("0.0.0.0:0")?,
+            targets,
+        })
+    }
+
+    /// Envia a mensagem para todos os destinos, ignorando falhas individuais
+    pub fn send(&self, message: &OscMessage) {
+        let packet = message.encode();
+        for target in &self.targets {
+            let _ = self.socket.send_to(&packet, target);
+        }
+    }
+}
+
+/// Emite o estado do timer por OSC sempre que ele muda
+pub struct OscOutput {
+    sender: OscSender,
+    last_snapshot: Option<TimerSnapshot>,
+}
+
+impl OscOutput {
+    pub fn new(sender: OscSender) -> Self {
+        OscOutput {
+            sender,
+            last_snapshot: None,
+        }
+    }
+
+    /// Envia `/remaining`, `/state` e `/threshold` quando os valores mudam
+    pub fn publish(&mut self, snapshot: &TimerSnapshot) {
+        let last = self.last_snapshot.as_ref();
+
+        if last.is_none_or(|last| last.text != snapshot.text) {
+            self.sender.send(&OscMessage::new(
+                &format!("{}/remaining", PREFIX),
+                vec![
+                    OscArg::Int((snapshot.remaining_ms / 1000) as i32),
+                    OscArg::Str(snapshot.text.clone()),
+                ],
+            ));
+        }
+
+        if last.is_none_or(|last| last.status != snapshot.status) {
+            self.sender.send(&OscMessage::new(
+                &format!("{}/state", PREFIX),
+                vec![OscArg::Str(snapshot.status.label().to_string())],
+            ));
+        }
+
+        if last.is_some_and(|last| last.alert_stage != snapshot.alert_stage) {
+            self.sender.send(&OscMessage::new(
+                &format!("{}/threshold", PREFIX),
+                vec![OscArg::Str(snapshot.alert_stage.label().to_string())],
+            ));
+        }
+
+        self.last_snapshot = Some(snapshot.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_loader::RegressivaConfig;
+    use crate::timer::Countdown;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn receive(socket: &UdpSocket) -> OscMessage {
+        let mut buffer = [0; 1536];
+        let (size, _) = socket.recv_from(&mut buffer).unwrap();
+        OscMessage::decode(&buffer[..size]).unwrap()
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        let message = OscMessage::new(
+            "/regressiva/remaining",
+            vec![
+                OscArg::Int(-42),
+                OscArg::Float(1.5),
+                OscArg::Str("01:30".to_string()),
+            ],
+        );
+        let packet = message.encode();
+        assert_eq!(packet.len() % 4, 0);
+        assert_eq!(OscMessage::decode(&packet).unwrap(), message);
+    }
+
+    #[test]
+    fn listener_applies_commands_from_udp() {
+        let (tx, rx) = mpsc::channel::<RemoteRequest>();
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = socket.local_addr().unwrap();
+        listen(socket, tx);
+
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let config = RegressivaConfig::default();
+        let mut countdown = Countdown::new(Duration::new(30, 0));
+
+        let apply = |countdown: &mut Countdown, message: OscMessage| {
+            client.send_to(&message.encode(), addr).unwrap();
+            let request = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+            let result = request.command.apply(countdown, &config);
+            let _ = request.reply.send(result);
+        };
+
+        apply(
+            &mut countdown,
+            OscMessage::new("/regressiva/set", vec![OscArg::Int(75)]),
+        );
+        apply(
+            &mut countdown,
+            OscMessage::new("/regressiva/nudge", vec![OscArg::Float(-15.0)]),
+        );
+        apply(
+            &mut countdown,
+            OscMessage::new("/regressiva/start", Vec::new()),
+        );
+        assert_eq!(countdown.duration, Duration::new(60, 0));
+        assert!(countdown.is_running);
+
+        apply(
+            &mut countdown,
+            OscMessage::new("/regressiva/preset", vec![OscArg::Str("9".to_string())]),
+        );
+        assert_eq!(countdown.duration, Duration::new(120, 0));
+        assert!(!countdown.is_running);
+    }
+
+    #[test]
+    fn output_emits_state_changes_and_thresholds() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let sender = OscSender::new(vec![receiver.local_addr().unwrap().to_string()]).unwrap();
+        let mut output = OscOutput::new(sender);
+
+        let config = RegressivaConfig::default();
+        let mut countdown = Countdown::new(Duration::new(16, 0));
+        output.publish(&countdown.snapshot(&config));
+        assert_eq!(receive(&receiver).address, "/regressiva/remaining");
+        let state = receive(&receiver);
+        assert_eq!(state.address, "/regressiva/state");
+        assert_eq!(state.args, vec![OscArg::Str("paused".to_string())]);
+
+        countdown.duration = Duration::new(15, 0);
+        output.publish(&countdown.snapshot(&config));
+        let remaining = receive(&receiver);
+        assert_eq!(remaining.args[0], OscArg::Int(15));
+        let threshold = receive(&receiver);
+        assert_eq!(threshold.address, "/regressiva/threshold");
+        assert_eq!(threshold.args, vec![OscArg::Str("warning".to_string())]);
+    }
+
+    #[test]
+    fn unknown_addresses_are_rejected() {
+        let message = OscMessage::new("/outro/start", Vec::new());
+        assert!(to_command(&message).is_err());
+        let message = OscMessage::new("/regressiva/set", Vec::new());
+        assert!(to_command(&message).is_err());
+    }
+}
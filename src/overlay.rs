@@ -0,0 +1,55 @@
+use crate::config_loader::RegressivaConfig;
+use crate::fonts::{self, FontSource};
+use crate::timer::{ALERT_COLOR, TIMER_COLOR};
+use sdl2::pixels::Color;
+use serde_json::json;
+use std::fs;
+
+/// Página HTML do overlay, com o marcador onde o estilo é injetado
+const PAGE_TEMPLATE: &str = include_str!("overlay.html");
+
+/// Altura da janela do timer, usada para converter o tamanho da fonte em vh
+const DISPLAY_HEIGHT: f64 = 720.0;
+
+/// Intervalo de consulta do estado pela página
+const POLL_INTERVAL_MS: u64 = 100;
+
+/// Recursos do overlay para fontes de navegador (OBS, vMix)
+pub struct Overlay {
+    page: String,
+    font: Option<Vec<u8>>,
+}
+
+impl Overlay {
+    /// Monta a página com as cores e fontes da configuração
+    pub fn from_config(config: &RegressivaConfig) -> Self {
+        let estilo = json!({
+            "cor_normal": css_color(TIMER_COLOR),
+            "cor_alerta": css_color(ALERT_COLOR),
+            "tamanho_vh": f64::from(config.fontes.get_fonte_gigante()) * 100.0 / DISPLAY_HEIGHT,
+            "intervalo_ms": POLL_INTERVAL_MS,
+        });
+
+        Overlay {
+            page: PAGE_TEMPLATE.replace("/*ESTILO*/", &estilo.to_string()),
+            font: match fonts::resolve(&config.fontes) {
+                FontSource::File(path) => fs::read(path).ok(),
+                FontSource::Embedded => None,
+            },
+        }
+    }
+
+    /// Retorna a página HTML do overlay
+    pub fn page(&self) -> &str {
+        &self.page
+    }
+
+    /// Retorna o arquivo da fonte usada no display, ou a fonte embutida
+    pub fn font(&self) -> &[u8] {
+        self.font.as_deref().unwrap_or(fonts::EMBEDDED_FONT)
+    }
+}
+
+fn css_color(color: Color) -> String {
+    format!("rgb({}, {}, {})", color.r, color.g, color.b)
+}
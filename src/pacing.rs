@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+/// Intervalo de consulta do estado recebido pela rede (réplica e modo seguidor), que não gera eventos do SDL
+pub const REMOTE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Próximo momento em que o loop principal precisa acordar, se nenhum evento chegar antes
+///
+/// Cada parte do programa informa o seu prazo (a próxima virada de segundo, o piscar do cursor,
+/// o próximo envio de uma integração...) e o loop espera eventos até o mais próximo deles.
+#[derive(Debug, Default)]
+pub struct FrameScheduler {
+    deadline: Option<Instant>,
+}
+
+impl FrameScheduler {
+    /// Acorda no instante pedido, se ele for antes do prazo atual
+    pub fn wake_at(&mut self, at: Instant) {
+        self.deadline = Some(self.deadline.map_or(at, |deadline| deadline.min(at)));
+    }
+
+    /// Acorda depois do intervalo pedido
+    pub fn wake_in(&mut self, now: Instant, delay: Duration) {
+        self.wake_at(now + delay);
+    }
+
+    /// Tempo de espera em milissegundos até o prazo mais próximo, limitado a `max`; os prazos
+    /// são descartados para o próximo quadro
+    pub fn take_timeout(&mut self, now: Instant, max: Duration) -> u32 {
+        let wait = self
+            .deadline
+            .take()
+            .map_or(max, |deadline| deadline.saturating_duration_since(now).min(max));
+        // Arredonda para cima para não acordar um pouco antes da virada do segundo
+        wait.as_micros().div_ceil(1000) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waits_until_the_nearest_deadline() {
+        let now = Instant::now();
+        let mut scheduler = FrameScheduler::default();
+        assert_eq!(scheduler.take_timeout(now, Duration::from_millis(500)), 500);
+
+        scheduler.wake_in(now, Duration::from_millis(300));
+        scheduler.wake_in(now, Duration::from_micros(120_400));
+        scheduler.wake_in(now, Duration::from_millis(450));
+        assert_eq!(scheduler.take_timeout(now, Duration::from_millis(500)), 121);
+
+        // Os prazos valem só para um quadro
+        assert_eq!(scheduler.take_timeout(now, Duration::from_millis(500)), 500);
+
+        scheduler.wake_in(now, Duration::from_secs(2));
+        assert_eq!(scheduler.take_timeout(now, Duration::from_millis(500)), 500);
+
+        // Um prazo já vencido não espera
+        scheduler.wake_at(now);
+        assert_eq!(
+            scheduler.take_timeout(now + Duration::from_millis(5), Duration::from_millis(500)),
+            0
+        );
+    }
+}
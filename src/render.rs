@@ -0,0 +1,400 @@
+use crate::config_loader::Renderizador;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Canvas, Texture};
+use sdl2::ttf::Font;
+use sdl2::video::Window;
+use sdl2::VideoSubsystem;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Quadros seguidos com falha do renderizador antes de trocá-lo pelo de software
+const FAILED_FRAMES_LIMIT: u32 = 30;
+
+/// Intervalo mínimo entre tentativas de recriar o renderizador ou a janela
+const REBUILD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Quadros desenhados sem usar uma textura antes de ela ser descartada
+const CACHE_KEEP_FRAMES: u64 = 60;
+
+/// Falha ao desenhar um elemento
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    /// O texto não pôde ser rasterizado (glifo inválido, fonte corrompida...); só o elemento é pulado
+    Text(String),
+    /// O renderizador falhou; repetida por vários quadros, leva à troca do renderizador
+    Renderer(String),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Text(e) => write!(f, "texto: {}", e),
+            RenderError::Renderer(e) => write!(f, "renderizador: {}", e),
+        }
+    }
+}
+
+// Erros de fill_rect, copy e demais chamadas do canvas
+impl From<String> for RenderError {
+    fn from(e: String) -> Self {
+        RenderError::Renderer(e)
+    }
+}
+
+// Fonte (pelo endereço), texto e cor de uma textura já rasterizada
+type TextKey = (usize, String, Color);
+
+struct CachedText {
+    texture: Texture,
+    width: u32,
+    height: u32,
+    last_frame: u64,
+}
+
+// Texturas dos textos já desenhados em uma janela, para não rasterizar tudo a cada quadro.
+// As texturas pertencem ao renderizador da janela: são destruídas com ele e a cache precisa
+// ser esvaziada antes de trocá-lo. A fonte é identificada pelo endereço, então a cache também
+// é esvaziada quando as fontes são recarregadas.
+#[derive(Default)]
+struct TextureCache {
+    textures: HashMap<TextKey, CachedText>,
+    frame: u64,
+}
+
+impl TextureCache {
+    // Textura do texto, rasterizada só na primeira vez que é pedida
+    fn get(
+        &mut self,
+        canvas: &Canvas<Window>,
+        font: &Font,
+        text: &str,
+        color: Color,
+    ) -> Result<&CachedText, RenderError> {
+        let key = (std::ptr::from_ref(font) as usize, text.to_string(), color);
+        let cached = match self.textures.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let surface = font
+                    .render(text)
+                    .blended(color)
+                    .map_err(|e| RenderError::Text(e.to_string()))?;
+                let texture = canvas
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| RenderError::Renderer(e.to_string()))?;
+                entry.insert(CachedText {
+                    texture,
+                    width: surface.width(),
+                    height: surface.height(),
+                    last_frame: self.frame,
+                })
+            }
+        };
+        cached.last_frame = self.frame;
+        Ok(cached)
+    }
+
+    // Fecha o quadro, destruindo as texturas que não são usadas há muitos quadros
+    fn end_frame(&mut self) {
+        let frame = self.frame;
+        let stale: Vec<TextKey> = self
+            .textures
+            .iter()
+            .filter(|(_, cached)| frame - cached.last_frame >= CACHE_KEEP_FRAMES)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            if let Some(cached) = self.textures.remove(&key) {
+                // O renderizador dono da textura ainda existe enquanto a cache tem entradas
+                unsafe { cached.texture.destroy() };
+            }
+        }
+        self.frame += 1;
+    }
+
+    // Destrói todas as texturas, com o renderizador ainda vivo
+    fn clear(&mut self) {
+        for (_, cached) in self.textures.drain() {
+            unsafe { cached.texture.destroy() };
+        }
+    }
+
+    // Esquece as texturas de um renderizador que já foi destruído junto com elas
+    fn forget(&mut self) {
+        self.textures.clear();
+    }
+}
+
+/// Canvas de um quadro em desenho, com a cache de textos da janela
+pub struct Painter<'a> {
+    pub canvas: &'a mut Canvas<Window>,
+    cache: &'a mut TextureCache,
+}
+
+impl Painter<'_> {
+    /// Desenha o texto centralizado no retângulo
+    pub fn text(
+        &mut self,
+        font: &Font,
+        text: &str,
+        color: Color,
+        text_rect: Rect,
+    ) -> Result<(), RenderError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let cached = self.cache.get(self.canvas, font, text, color)?;
+        let centered_rect = Rect::new(
+            text_rect.x() + (text_rect.width() as i32 - cached.width as i32) / 2,
+            text_rect.y() + (text_rect.height() as i32 - cached.height as i32) / 2,
+            cached.width,
+            cached.height,
+        );
+
+        self.canvas.copy(&cached.texture, None, Some(centered_rect))?;
+        Ok(())
+    }
+
+    /// Preenche o retângulo com a cor
+    pub fn fill(&mut self, rect: Rect, color: Color) -> Result<(), RenderError> {
+        self.canvas.set_draw_color(color);
+        self.canvas.fill_rect(rect)?;
+        Ok(())
+    }
+
+    /// Desenha o contorno do retângulo com a cor
+    pub fn outline(&mut self, rect: Rect, color: Color) -> Result<(), RenderError> {
+        self.canvas.set_draw_color(color);
+        self.canvas.draw_rect(rect)?;
+        Ok(())
+    }
+}
+
+/// Elementos com falha, para registrar cada problema uma vez e não a cada quadro
+#[derive(Debug, Default)]
+pub struct FailingElements {
+    failing: BTreeSet<&'static str>,
+}
+
+impl FailingElements {
+    /// Registra o resultado do elemento; retorna verdadeiro se a falha é nova
+    pub fn report(&mut self, window: &str, element: &'static str, result: &Result<(), RenderError>) -> bool {
+        match result {
+            Ok(()) => {
+                if self.failing.remove(element) {
+                    log::info!("{} voltou a ser desenhado na janela {}", element, window);
+                }
+                false
+            }
+            Err(e) => {
+                let new = self.failing.insert(element);
+                if new {
+                    log::error!("Falha ao desenhar {} na janela {}: {}", element, window, e);
+                }
+                new
+            }
+        }
+    }
+}
+
+/// Janela com o seu canvas, que pula os elementos com falha e troca de renderizador se ele quebrar
+pub struct WindowCanvas {
+    video: VideoSubsystem,
+    title: String,
+    width: u32,
+    height: u32,
+    canvas: Option<Canvas<Window>>,
+    cache: TextureCache,
+    failing: FailingElements,
+    renderer_failed: bool,
+    redraw: bool,
+    failed_frames: u32,
+    last_rebuild: Option<Instant>,
+}
+
+impl WindowCanvas {
+    /// Cria a janela centralizada e sem bordas com o renderizador configurado, sincronizado com o
+    /// monitor; se ele não estiver disponível, tenta o renderizador por software
+    pub fn new(
+        video: &VideoSubsystem,
+        title: &str,
+        width: u32,
+        height: u32,
+        renderer: &Renderizador,
+    ) -> Result<Self, String> {
+        let mut window_canvas = WindowCanvas {
+            video: video.clone(),
+            title: title.to_string(),
+            width,
+            height,
+            canvas: None,
+            cache: TextureCache::default(),
+            failing: FailingElements::default(),
+            renderer_failed: false,
+            redraw: true,
+            failed_frames: 0,
+            last_rebuild: None,
+        };
+        let canvas = match build_canvas(window_canvas.build_window()?, renderer) {
+            Ok(canvas) => canvas,
+            Err(e) if *renderer != Renderizador::Software => {
+                log::warn!(
+                    "Renderizador {:?} indisponível na janela {}: {}; tentando o renderizador por software",
+                    renderer,
+                    title,
+                    e
+                );
+                build_canvas(window_canvas.build_window()?, &Renderizador::Software)?
+            }
+            Err(e) => return Err(e),
+        };
+        log::info!("Janela {} criada com o renderizador {}", title, canvas.info().name);
+        window_canvas.canvas = Some(canvas);
+        Ok(window_canvas)
+    }
+
+    /// Começa um quadro limpando a janela com a cor de fundo
+    pub fn begin(&mut self, background: Color) {
+        self.renderer_failed = false;
+        self.redraw = false;
+        if self.canvas.is_none() {
+            self.rebuild();
+        }
+        if let Some(canvas) = self.canvas.as_mut() {
+            canvas.set_draw_color(background);
+            canvas.clear();
+        }
+    }
+
+    /// Desenha um elemento; se ele falhar, o restante do quadro continua sendo desenhado
+    pub fn draw<F>(&mut self, element: &'static str, draw: F)
+    where
+        F: FnOnce(&mut Painter) -> Result<(), RenderError>,
+    {
+        let Some(canvas) = self.canvas.as_mut() else {
+            return;
+        };
+        let result = draw(&mut Painter {
+            canvas,
+            cache: &mut self.cache,
+        });
+        if let Err(RenderError::Renderer(_)) = result {
+            self.renderer_failed = true;
+        }
+        self.failing.report(&self.title, element, &result);
+    }
+
+    /// Mostra o quadro; depois de muitos quadros seguidos com falha, recria o renderizador por software
+    pub fn present(&mut self) {
+        if let Some(canvas) = self.canvas.as_mut() {
+            canvas.present();
+            self.cache.end_frame();
+        }
+        if !self.renderer_failed {
+            self.failed_frames = 0;
+            return;
+        }
+        self.failed_frames += 1;
+        if self.failed_frames >= FAILED_FRAMES_LIMIT {
+            self.failed_frames = 0;
+            self.rebuild();
+        }
+    }
+
+    /// Indica se a janela precisa ser desenhada mesmo sem mudanças: depois de ser recriada ou de
+    /// um quadro com falha do renderizador, que conta para a troca pelo de software
+    pub fn needs_redraw(&self) -> bool {
+        self.canvas.is_none() || self.renderer_failed || self.redraw
+    }
+
+    /// Descarta os textos já rasterizados, como ao recarregar as fontes
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+
+    // Troca o renderizador pelo de software, recriando a janela se ela tiver sido perdida
+    fn rebuild(&mut self) {
+        if self
+            .last_rebuild
+            .is_some_and(|last| last.elapsed() < REBUILD_INTERVAL)
+        {
+            return;
+        }
+        self.last_rebuild = Some(Instant::now());
+
+        // As texturas são destruídas junto com o renderizador antigo
+        self.cache.forget();
+        let window = match self.canvas.take() {
+            Some(canvas) => Ok(canvas.into_window()),
+            None => self.build_window(),
+        };
+        let canvas = window.and_then(|window| build_canvas(window, &Renderizador::Software));
+        match canvas {
+            Ok(canvas) => {
+                log::warn!("Janela {} usando o renderizador por software", self.title);
+                self.canvas = Some(canvas);
+                self.redraw = true;
+            }
+            Err(e) => log::error!("Falha ao recriar a janela {}: {}", self.title, e),
+        }
+    }
+
+    fn build_window(&self) -> Result<Window, String> {
+        self.video
+            .window(&self.title, self.width, self.height)
+            .position_centered()
+            .borderless()
+            .build()
+            .map_err(|e| e.to_string())
+    }
+}
+
+// Cria o canvas da janela com o renderizador pedido
+fn build_canvas(window: Window, renderer: &Renderizador) -> Result<Canvas<Window>, String> {
+    let builder = window.into_canvas().present_vsync();
+    let builder = match renderer {
+        Renderizador::Acelerado => builder.accelerated(),
+        Renderizador::Software => builder.software(),
+        Renderizador::Driver(name) => builder.index(driver_index(name)?),
+    };
+    let mut canvas = builder.build().map_err(|e| e.to_string())?;
+    // Sem mistura, a transparência das cores seria ignorada
+    canvas.set_blend_mode(BlendMode::Blend);
+    Ok(canvas)
+}
+
+/// Posição do driver de renderização na lista do SDL, sem diferenciar maiúsculas
+pub fn driver_index(name: &str) -> Result<u32, String> {
+    let drivers: Vec<&str> = sdl2::render::drivers().map(|info| info.name).collect();
+    drivers
+        .iter()
+        .position(|driver| driver.eq_ignore_ascii_case(name))
+        .map(|index| index as u32)
+        .ok_or_else(|| {
+            format!(
+                "driver de renderização {:?} desconhecido; disponíveis: {}",
+                name,
+                drivers.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_are_reported_once_until_the_element_recovers() {
+        let mut failing = FailingElements::default();
+        let error = Err(RenderError::Text("glifo inválido".to_string()));
+
+        assert!(failing.report("Regressiva", "creditos", &error));
+        assert!(!failing.report("Regressiva", "creditos", &error));
+        assert!(failing.report("Regressiva", "timer", &error));
+        assert!(!failing.report("Regressiva", "creditos", &Ok(())));
+        assert!(failing.report("Regressiva", "creditos", &error));
+    }
+}
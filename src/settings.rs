@@ -0,0 +1,495 @@
+use crate::color::ConfigColor;
+use crate::config_loader::RegressivaConfig;
+use crate::config_validation;
+use crate::i18n::Texts;
+use crate::text_output;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use std::fs;
+use std::path::Path;
+use toml::Value;
+use toml_edit::DocumentMut;
+
+// Layout da tela de ajustes na janela de controle (1280x720)
+const COLUMN_X: [i32; 3] = [20, 440, 860];
+const FIRST_ROW_Y: i32 = 60;
+const ROW_HEIGHT: i32 = 38;
+const LABEL_WIDTH: u32 = 230;
+const VALUE_WIDTH: u32 = 160;
+const SWATCH_WIDTH: u32 = 30;
+
+/// Tipo de valor editado em um campo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Number,
+    Text,
+    Color,
+    Toggle,
+}
+
+/// Campo da tela de ajustes, ligado a uma chave de uma seção do Config.toml
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub label: String,
+    pub kind: FieldKind,
+    pub value: String,
+    section: &'static str,
+    key: String,
+    column: usize,
+    row: usize,
+}
+
+impl Field {
+    /// Área do rótulo do campo
+    pub fn label_rect(&self) -> Rect {
+        Rect::new(
+            COLUMN_X[self.column],
+            FIRST_ROW_Y + self.row as i32 * ROW_HEIGHT,
+            LABEL_WIDTH,
+            (ROW_HEIGHT - 6) as u32,
+        )
+    }
+
+    /// Área do valor do campo, onde o operador clica para editar
+    pub fn value_rect(&self) -> Rect {
+        let width = if self.kind == FieldKind::Color {
+            VALUE_WIDTH - SWATCH_WIDTH - 6
+        } else {
+            VALUE_WIDTH
+        };
+        Rect::new(
+            COLUMN_X[self.column] + LABEL_WIDTH as i32,
+            FIRST_ROW_Y + self.row as i32 * ROW_HEIGHT,
+            width,
+            (ROW_HEIGHT - 6) as u32,
+        )
+    }
+
+    /// Amostra da cor, para os campos de cor com valor válido
+    pub fn swatch(&self) -> Option<(Rect, Color)> {
+        if self.kind != FieldKind::Color {
+            return None;
+        }
+        let color = ConfigColor::parse(&self.value).ok()?;
+        let rect = Rect::new(
+            COLUMN_X[self.column] + (LABEL_WIDTH + VALUE_WIDTH - SWATCH_WIDTH) as i32,
+            FIRST_ROW_Y + self.row as i32 * ROW_HEIGHT,
+            SWATCH_WIDTH,
+            (ROW_HEIGHT - 6) as u32,
+        );
+        Some((rect, color.to_color(255)))
+    }
+
+    // Converte o texto digitado no valor TOML da chave; `yes` é a palavra que liga uma opção
+    fn to_value(&self, yes: &str) -> Result<Value, String> {
+        let invalid = |expected: &str| format!("{}: {}", self.label, expected);
+        match self.kind {
+            FieldKind::Number => self
+                .value
+                .trim()
+                .parse::<i64>()
+                .map(Value::Integer)
+                .map_err(|_| invalid("informe um número inteiro")),
+            FieldKind::Text => Ok(Value::String(self.value.clone())),
+            FieldKind::Color => ConfigColor::parse(&self.value)
+                .map(|_| Value::String(self.value.trim().to_string()))
+                .map_err(|e| invalid(&e)),
+            FieldKind::Toggle => Ok(Value::Boolean(self.value == yes)),
+        }
+    }
+}
+
+/// O que fazer depois de um clique na tela de ajustes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsAction {
+    Save,
+    Cancel,
+}
+
+/// Tela de ajustes: edita presets, textos, cores, fontes e botões sem abrir o Config.toml
+pub struct SettingsEditor {
+    fields: Vec<Field>,
+    focused: Option<usize>,
+    yes: String,
+    no: String,
+    pub message: Option<String>,
+}
+
+impl SettingsEditor {
+    /// Área do botão que abre a tela de ajustes
+    pub fn open_button() -> Rect {
+        Rect::new(1140, 670, 130, 40)
+    }
+
+    /// Área do botão de salvar
+    pub fn save_button() -> Rect {
+        Rect::new(440, 610, 190, 40)
+    }
+
+    /// Área do botão de cancelar
+    pub fn cancel_button() -> Rect {
+        Rect::new(650, 610, 190, 40)
+    }
+
+    /// Área das mensagens de erro
+    pub fn message_rect() -> Rect {
+        Rect::new(20, 660, 1100, 50)
+    }
+
+    /// Abre a tela com os valores da configuração atual e os rótulos no idioma configurado
+    pub fn open(config: &RegressivaConfig, texts: &Texts) -> Self {
+        let current = Value::try_from(config).unwrap_or(Value::Table(Default::default()));
+        let yes = texts.get("ajustes_sim").to_string();
+        let no = texts.get("ajustes_nao").to_string();
+        let mut fields = Vec::new();
+        let mut add = |column: usize, label: String, section: &'static str, key: String, kind| {
+            let row = fields
+                .iter()
+                .filter(|f: &&Field| f.column == column)
+                .count();
+            let value = current
+                .get(section)
+                .and_then(|table| table.get(&key))
+                .map(|value| display_value(value, kind, &yes, &no))
+                .unwrap_or_default();
+            fields.push(Field {
+                label,
+                kind,
+                value,
+                section,
+                key,
+                column,
+                row,
+            });
+        };
+
+        for i in 1..=9 {
+            add(
+                0,
+                texts.format("ajustes_tempo", &[("n", &i.to_string())]),
+                "tempo",
+                format!("tempo{}", i),
+                FieldKind::Number,
+            );
+        }
+        add(
+            0,
+            texts.get("ajustes_alerta").to_string(),
+            "tempo",
+            "tempo_alerta_regressiva".to_string(),
+            FieldKind::Number,
+        );
+        add(
+            0,
+            texts.get("ajustes_reset").to_string(),
+            "tempo",
+            "tempo_reset".to_string(),
+            FieldKind::Number,
+        );
+
+        for i in 1..=9 {
+            add(
+                1,
+                texts.format("ajustes_texto", &[("n", &i.to_string())]),
+                "tempo_texto",
+                format!("tempo_texto{}", i),
+                FieldKind::Text,
+            );
+        }
+        add(
+            1,
+            texts.get("ajustes_arquivo_fonte").to_string(),
+            "fontes",
+            "fonte".to_string(),
+            FieldKind::Text,
+        );
+        for key in ["fonte_gigante", "fonte_grande", "fonte_media", "fonte_pequena"] {
+            add(
+                1,
+                texts.get(&format!("ajustes_{}", key)).to_string(),
+                "fontes",
+                key.to_string(),
+                FieldKind::Number,
+            );
+        }
+
+        for key in [
+            "cor_background",
+            "cor_botoes",
+            "cor_texto",
+            "cor_vermelho",
+            "cor_verde",
+            "cor_azul",
+        ] {
+            add(
+                2,
+                texts.get(&format!("ajustes_{}", key)).to_string(),
+                "cores",
+                key.to_string(),
+                FieldKind::Color,
+            );
+        }
+        for (section, key) in [
+            ("botoes", "botao_iniciar"),
+            ("botoes", "botao_pausar"),
+            ("botoes", "botao_reset"),
+            ("creditos", "mostrar_creditos"),
+        ] {
+            add(
+                2,
+                texts.get(&format!("ajustes_{}", key)).to_string(),
+                section,
+                key.to_string(),
+                FieldKind::Toggle,
+            );
+        }
+
+        SettingsEditor {
+            fields,
+            focused: None,
+            yes,
+            no,
+            message: None,
+        }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Índice do campo em edição
+    pub fn focused(&self) -> Option<usize> {
+        self.focused
+    }
+
+    /// Trata um clique: foca um campo, alterna uma opção ou aciona salvar/cancelar
+    pub fn click(&mut self, x: i32, y: i32) -> Option<SettingsAction> {
+        if Self::save_button().contains_point((x, y)) {
+            return Some(SettingsAction::Save);
+        }
+        if Self::cancel_button().contains_point((x, y)) {
+            return Some(SettingsAction::Cancel);
+        }
+
+        self.focused = None;
+        let index = self
+            .fields
+            .iter()
+            .position(|field| field.value_rect().contains_point((x, y)))?;
+        let field = &mut self.fields[index];
+        if field.kind == FieldKind::Toggle {
+            field.value = if field.value == self.yes {
+                self.no.clone()
+            } else {
+                self.yes.clone()
+            };
+        } else {
+            self.focused = Some(index);
+        }
+        None
+    }
+
+    /// Acrescenta o texto digitado ao campo em edição
+    pub fn type_text(&mut self, text: &str) {
+        if let Some(index) = self.focused {
+            self.fields[index].value.push_str(text);
+        }
+    }
+
+    /// Apaga o último caractere do campo em edição
+    pub fn backspace(&mut self) {
+        if let Some(index) = self.focused {
+            self.fields[index].value.pop();
+        }
+    }
+
+    /// Encerra a edição do campo atual
+    pub fn unfocus(&mut self) {
+        self.focused = None;
+    }
+
+    /// Grava os campos da tela no arquivo de configuração, mantendo comentários e a ordem das chaves
+    pub fn save(&self, new_config: &RegressivaConfig, config_path: &Path) -> Result<(), String> {
+        let source = fs::read_to_string(config_path).unwrap_or_default();
+        // Sem arquivo para editar, grava a configuração inteira
+        let contents = if source.trim().is_empty() {
+            new_config.to_toml_string().map_err(|e| e.to_string())?
+        } else {
+            self.edit_document(&source, new_config)?
+        };
+        // O observador do arquivo não pode ler a configuração pela metade
+        text_output::write_atomic(config_path, &contents).map_err(|e| e.to_string())
+    }
+
+    // Troca no documento só os valores das chaves exibidas na tela
+    fn edit_document(&self, source: &str, new_config: &RegressivaConfig) -> Result<String, String> {
+        let mut document = source.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+        let values = Value::try_from(new_config).map_err(|e| e.to_string())?;
+        for field in &self.fields {
+            let Some(value) = values.get(field.section).and_then(|table| table.get(&field.key))
+            else {
+                continue;
+            };
+            let mut new_value = value
+                .to_string()
+                .parse::<toml_edit::Value>()
+                .map_err(|e| e.to_string())?;
+
+            let section = document
+                .entry(field.section)
+                .or_insert_with(toml_edit::table)
+                .as_table_like_mut()
+                .ok_or_else(|| format!("{} não é uma seção", field.section))?;
+            // Mantém os comentários escritos ao lado do valor antigo
+            if let Some(old_value) = section.get(&field.key).and_then(|item| item.as_value()) {
+                *new_value.decor_mut() = old_value.decor().clone();
+            }
+            section.insert(&field.key, toml_edit::Item::Value(new_value));
+        }
+        Ok(document.to_string())
+    }
+
+    /// Monta a nova configuração a partir da atual e valida o resultado
+    pub fn build(&self, config: &RegressivaConfig) -> Result<RegressivaConfig, String> {
+        let mut value = Value::try_from(config).map_err(|e| e.to_string())?;
+        for field in &self.fields {
+            let new_value = field.to_value(&self.yes)?;
+            if let Some(table) = value.get_mut(field.section).and_then(Value::as_table_mut) {
+                table.insert(field.key.clone(), new_value);
+            }
+        }
+
+        let new_config: RegressivaConfig = value
+            .try_into()
+            .map_err(|e: toml::de::Error| e.message().to_string())?;
+        match config_validation::validate(&new_config, "").first() {
+            Some(problem) => Err(format!(
+                "{}: {}",
+                problem.field.as_deref().unwrap_or_default(),
+                problem.message
+            )),
+            None => Ok(new_config),
+        }
+    }
+}
+
+// Texto exibido no campo para um valor do Config.toml
+fn display_value(value: &Value, kind: FieldKind, yes: &str, no: &str) -> String {
+    match (kind, value) {
+        (FieldKind::Toggle, Value::Boolean(enabled)) => {
+            if *enabled { yes } else { no }.to_string()
+        }
+        (_, Value::String(text)) => text.clone(),
+        (_, other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts() -> Texts {
+        Texts::new(&Default::default())
+    }
+
+    fn validated(source: &str) -> RegressivaConfig {
+        config_validation::parse(source, Path::new("Config.toml")).unwrap()
+    }
+
+    fn field_index(editor: &SettingsEditor, label: &str) -> usize {
+        editor
+            .fields()
+            .iter()
+            .position(|f| f.label == label)
+            .unwrap()
+    }
+
+    fn edit(editor: &mut SettingsEditor, label: &str, text: &str) {
+        let rect = editor.fields()[field_index(editor, label)].value_rect();
+        editor.click(rect.x() + 1, rect.y() + 1);
+        while !editor.fields()[editor.focused().unwrap()].value.is_empty() {
+            editor.backspace();
+        }
+        editor.type_text(text);
+        editor.unfocus();
+    }
+
+    #[test]
+    fn edits_are_applied_to_a_copy_of_the_config() {
+        let config = RegressivaConfig::default();
+        let mut editor = SettingsEditor::open(&config, &texts());
+        assert_eq!(
+            editor.fields()[field_index(&editor, "Cor de fundo")].value,
+            "#093D53"
+        );
+
+        edit(&mut editor, "Tempo 9 (s)", "180");
+        edit(&mut editor, "Texto 9", "03:00");
+        edit(&mut editor, "Cor de fundo", "black");
+        let toggle = editor.fields()[field_index(&editor, "Botão reset")].value_rect();
+        assert_eq!(editor.click(toggle.x() + 1, toggle.y() + 1), None);
+
+        let new_config = editor.build(&config).unwrap();
+        assert_eq!(new_config.tempo.get_tempo(9), 180);
+        assert_eq!(new_config.tempo_texto.get_tempo_texto(9), "03:00");
+        assert_eq!(new_config.cores.get_background_color(), Color::RGB(0, 0, 0));
+        assert!(!new_config.botoes.mostrar_botao_reset());
+        assert_eq!(config.tempo.get_tempo(9), 120);
+    }
+
+    #[test]
+    fn saving_keeps_comments_and_key_order() {
+        let source = "# Debate municipal\n[tempo]\ntempo9 = 120 # réplica\ntempo1 = 0\n\n[cores]\ncor_texto = [0, 0, 0]\n";
+        let config = validated(source);
+        let mut editor = SettingsEditor::open(&config, &texts());
+        edit(&mut editor, "Tempo 9 (s)", "180");
+        edit(&mut editor, "Texto 9", "03:00");
+        edit(&mut editor, "Cor do texto", "white");
+
+        let new_config = editor.build(&config).unwrap();
+        let saved = editor.edit_document(source, &new_config).unwrap();
+        assert!(saved.starts_with("# Debate municipal\n[tempo]\ntempo9 = 180 # réplica\ntempo1 = 0\n"));
+        assert_eq!(validated(&saved).tempo.get_tempo(9), 180);
+        assert_eq!(
+            validated(&saved).cores.get_text_color(),
+            Color::RGB(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn labels_and_toggles_follow_the_language() {
+        let config: RegressivaConfig = toml::from_str("[idioma]\nidioma = \"en\"").unwrap();
+        let mut editor = SettingsEditor::open(&config, &Texts::new(&config.idioma));
+        let index = field_index(&editor, "Show credits");
+        assert_eq!(editor.fields()[index].value, "yes");
+
+        let toggle = editor.fields()[index].value_rect();
+        editor.click(toggle.x() + 1, toggle.y() + 1);
+        assert_eq!(editor.fields()[index].value, "no");
+        assert!(!editor.build(&config).unwrap().creditos.mostrar_creditos());
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        let config = RegressivaConfig::default();
+        let mut editor = SettingsEditor::open(&config, &texts());
+        edit(&mut editor, "Cor do texto", "#00000");
+        assert!(editor
+            .build(&config)
+            .unwrap_err()
+            .starts_with("Cor do texto"));
+
+        let mut editor = SettingsEditor::open(&config, &texts());
+        edit(&mut editor, "Alerta (s)", "500");
+        assert!(editor
+            .build(&config)
+            .unwrap_err()
+            .starts_with("tempo.tempo_alerta_regressiva"));
+
+        let save = SettingsEditor::save_button();
+        assert_eq!(
+            editor.click(save.x() + 1, save.y() + 1),
+            Some(SettingsAction::Save)
+        );
+    }
+}
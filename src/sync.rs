@@ -0,0 +1,331 @@
+use crate::config_loader::SincroniaConfig;
+use crate::timer::Countdown;
+use crate::ws_server::{self, Broadcaster, MessageHandler};
+use serde_derive::{Deserialize, Serialize};
+use std::io::{self, ErrorKind};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::Message;
+
+/// Intervalo entre as medições de latência feitas pela réplica
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Espera entre tentativas de reconexão ao primário
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Estado do motor do timer replicado entre as instâncias
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineState {
+    pub remaining_ms: u64,
+    pub is_running: bool,
+    pub speaker: Option<String>,
+    pub segment: Option<String>,
+    pub segment_index: Option<usize>,
+}
+
+impl EngineState {
+    pub fn from_countdown(countdown: &Countdown) -> Self {
+        EngineState {
+            remaining_ms: countdown.duration.as_millis() as u64,
+            is_running: countdown.is_running,
+            speaker: countdown.speaker.clone(),
+            segment: countdown.segment.clone(),
+            segment_index: countdown.segment_index,
+        }
+    }
+
+    /// Aplica o estado descontando o tempo que ele levou para chegar
+    pub fn apply_to(&self, countdown: &mut Countdown, delay: Duration) {
+        let remaining = Duration::from_millis(self.remaining_ms);
+        countdown.duration = if self.is_running {
+            remaining.saturating_sub(delay)
+        } else {
+            remaining
+        };
+        countdown.is_running = self.is_running;
+        countdown.start_time = Instant::now();
+        countdown.speaker = self.speaker.clone();
+        countdown.segment = self.segment.clone();
+        countdown.segment_index = self.segment_index;
+    }
+
+    // Mudanças que precisam ser enviadas imediatamente, sem esperar o intervalo
+    fn differs_from(&self, other: &EngineState) -> bool {
+        self.is_running != other.is_running
+            || self.speaker != other.speaker
+            || self.segment != other.segment
+            || (!self.is_running && self.remaining_ms != other.remaining_ms)
+    }
+}
+
+/// Mensagens trocadas entre o primário e as réplicas
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SyncMessage {
+    State(EngineState),
+    Ping { id: u64 },
+    Pong { id: u64 },
+}
+
+/// Instância primária: transmite o estado do motor para as réplicas
+pub struct SyncPrimary {
+    broadcaster: Broadcaster,
+    last_state: Option<EngineState>,
+    last_sent: Instant,
+    interval: Duration,
+}
+
+impl SyncPrimary {
+    /// Inicia o servidor de sincronia no endereço de escuta configurado
+    pub fn start(config: &SincroniaConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.get_escuta())?;
+        Ok(SyncPrimary::serve(listener, config.get_intervalo()))
+    }
+
+    pub fn serve(listener: TcpListener, interval: Duration) -> Self {
+        // O primário só responde às medições de latência das réplicas
+        let handler: MessageHandler = Arc::new(|text| match serde_json::from_str(text) {
+            Ok(SyncMessage::Ping { id }) => serde_json::to_string(&SyncMessage::Pong { id }).ok(),
+            _ => None,
+        });
+
+        SyncPrimary {
+            broadcaster: ws_server::serve_with(listener, handler),
+            last_state: None,
+            last_sent: Instant::now(),
+            interval,
+        }
+    }
+
+    /// Envia o estado quando ele muda e, no intervalo configurado, como sinal de vida
+    pub fn publish(&mut self, countdown: &Countdown) {
+        let state = EngineState::from_countdown(countdown);
+        let changed = self
+            .last_state
+            .as_ref()
+            .is_none_or(|last| last.differs_from(&state));
+
+        if !changed && self.last_sent.elapsed() < self.interval {
+            return;
+        }
+
+        if let Ok(message) = serde_json::to_string(&SyncMessage::State(state.clone())) {
+            self.broadcaster.publish(&message);
+        }
+        self.last_state = Some(state);
+        self.last_sent = Instant::now();
+    }
+
+    /// Retorna quando o próximo sinal de vida é devido
+    pub fn next_publish(&self) -> Instant {
+        self.last_sent + self.interval
+    }
+}
+
+// Estado compartilhado entre a thread de conexão e o loop principal
+#[derive(Default)]
+struct ReplicaShared {
+    pending: Option<(EngineState, Instant)>,
+    last_received: Option<Instant>,
+    latency: Duration,
+}
+
+/// Réplica: espelha o estado de um primário e detecta a queda do link
+pub struct SyncReplica {
+    shared: Arc<Mutex<ReplicaShared>>,
+    stop: Arc<AtomicBool>,
+    timeout: Duration,
+}
+
+impl SyncReplica {
+    /// Conecta ao primário configurado em uma thread separada
+    pub fn start(config: &SincroniaConfig) -> Self {
+        SyncReplica::connect(config.get_primario(), config.get_tempo_limite())
+    }
+
+    pub fn connect(primary: &str, timeout: Duration) -> Self {
+        let replica = SyncReplica {
+            shared: Arc::new(Mutex::new(ReplicaShared::default())),
+            stop: Arc::new(AtomicBool::new(false)),
+            timeout,
+        };
+
+        let url = format!("ws://{}", primary);
+        let shared = replica.shared.clone();
+        let stop = replica.stop.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                follow(&url, &shared, &stop);
+                thread::sleep(RECONNECT_DELAY);
+            }
+        });
+
+        replica
+    }
+
+    /// Aplica o último estado recebido, compensando a latência do link
+    pub fn apply(&self, countdown: &mut Countdown) -> bool {
+        let mut shared = self.shared.lock().unwrap();
+        let latency = shared.latency;
+        match shared.pending.take() {
+            Some((state, received_at)) => {
+                state.apply_to(countdown, latency + received_at.elapsed());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Retorna se o primário parou de responder depois de já ter sido visto
+    pub fn link_lost(&self) -> bool {
+        self.shared
+            .lock()
+            .unwrap()
+            .last_received
+            .is_some_and(|last| last.elapsed() > self.timeout)
+    }
+
+    /// Retorna se a réplica já recebeu algum estado do primário
+    pub fn connected(&self) -> bool {
+        self.shared.lock().unwrap().last_received.is_some() && !self.link_lost()
+    }
+}
+
+impl Drop for SyncReplica {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// Segue o primário até a conexão cair ou a réplica ser encerrada
+fn follow(url: &str, shared: &Mutex<ReplicaShared>, stop: &AtomicBool) {
+    let Ok((mut socket, _)) = tungstenite::connect(url) else {
+        return;
+    };
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        let _ = stream.set_read_timeout(Some(Duration::from_millis(20)));
+    }
+
+    let mut next_ping_id = 0;
+    let mut ping_sent: Option<(u64, Instant)> = None;
+    let mut last_ping = Instant::now() - PING_INTERVAL;
+
+    while !stop.load(Ordering::Relaxed) {
+        if last_ping.elapsed() >= PING_INTERVAL {
+            next_ping_id += 1;
+            let ping = SyncMessage::Ping { id: next_ping_id };
+            if let Ok(text) = serde_json::to_string(&ping) {
+                if socket.send(Message::Text(text)).is_err() {
+                    return;
+                }
+            }
+            ping_sent = Some((next_ping_id, Instant::now()));
+            last_ping = Instant::now();
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(SyncMessage::State(state)) => {
+                    let mut shared = shared.lock().unwrap();
+                    shared.pending = Some((state, Instant::now()));
+                    shared.last_received = Some(Instant::now());
+                }
+                Ok(SyncMessage::Pong { id }) => {
+                    if let Some((sent_id, sent_at)) = ping_sent {
+                        if sent_id == id {
+                            // Latência de ida estimada como metade do tempo de ida e volta
+                            shared.lock().unwrap().latency = sent_at.elapsed() / 2;
+                            ping_sent = None;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Message::Close(_)) => return,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+
+    let _ = socket.close(None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Publica o estado do primário até a réplica recebê-lo
+    fn wait_for_update(
+        primary: &mut SyncPrimary,
+        replica: &SyncReplica,
+        source: &Countdown,
+        target: &mut Countdown,
+    ) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !replica.apply(target) {
+            assert!(Instant::now() < deadline, "a réplica não recebeu o estado");
+            primary.publish(source);
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn replica_mirrors_primary_and_detects_link_loss() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let mut primary = SyncPrimary::serve(listener, Duration::from_millis(50));
+        let replica = SyncReplica::connect(&addr, Duration::from_millis(300));
+        assert!(!replica.link_lost());
+
+        let mut source = Countdown::new(Duration::new(60, 0));
+        source.is_running = true;
+        source.speaker = Some("Ana".to_string());
+        let mut target = Countdown::new(Duration::new(30, 0));
+
+        wait_for_update(&mut primary, &replica, &source, &mut target);
+        assert!(target.is_running);
+        assert_eq!(target.speaker.as_deref(), Some("Ana"));
+        assert!(target.duration <= Duration::new(60, 0));
+        assert!(target.duration > Duration::new(59, 0));
+        assert!(replica.connected());
+
+        source.is_running = false;
+        source.duration = Duration::new(45, 0);
+        wait_for_update(&mut primary, &replica, &source, &mut target);
+        assert!(!target.is_running);
+        assert_eq!(target.duration, Duration::new(45, 0));
+
+        // Sem sinais de vida do primário, a réplica considera o link perdido
+        thread::sleep(Duration::from_millis(400));
+        assert!(replica.link_lost());
+        assert!(!replica.connected());
+    }
+
+    #[test]
+    fn paused_state_is_applied_without_compensation() {
+        let state = EngineState {
+            remaining_ms: 10_000,
+            is_running: false,
+            speaker: None,
+            segment: Some("Abertura".to_string()),
+            segment_index: Some(0),
+        };
+        let mut countdown = Countdown::new(Duration::ZERO);
+        state.apply_to(&mut countdown, Duration::from_millis(500));
+        assert_eq!(countdown.duration, Duration::new(10, 0));
+        assert_eq!(countdown.segment_index, Some(0));
+
+        let running = EngineState {
+            is_running: true,
+            ..state
+        };
+        running.apply_to(&mut countdown, Duration::from_millis(500));
+        assert_eq!(countdown.duration, Duration::from_millis(9_500));
+    }
+}
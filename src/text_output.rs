@@ -0,0 +1,113 @@
+use crate::config_loader::ArquivosTextoConfig;
+use crate::timer::TimerSnapshot;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Campo do estado gravado em cada arquivo
+#[derive(Clone, Copy)]
+enum Field {
+    Remaining,
+    Speaker,
+    Segment,
+}
+
+// Arquivo de saída e o último valor gravado nele
+struct TextFile {
+    path: PathBuf,
+    field: Field,
+    last_value: Option<String>,
+}
+
+/// Grava o estado em arquivos de texto para fontes como o "Text (read from file)" do OBS
+pub struct TextOutput {
+    files: Vec<TextFile>,
+}
+
+impl TextOutput {
+    pub fn new(config: &ArquivosTextoConfig) -> Self {
+        let files = [
+            (config.get_arquivo_tempo(), Field::Remaining),
+            (config.get_arquivo_orador(), Field::Speaker),
+            (config.get_arquivo_segmento(), Field::Segment),
+        ]
+        .into_iter()
+        .filter(|(path, _)| !path.is_empty())
+        .map(|(path, field)| TextFile {
+            path: PathBuf::from(path),
+            field,
+            last_value: None,
+        })
+        .collect();
+
+        TextOutput { files }
+    }
+
+    /// Regrava os arquivos cujo valor exibido mudou
+    pub fn publish(&mut self, snapshot: &TimerSnapshot) {
+        for file in &mut self.files {
+            let value = match file.field {
+                Field::Remaining => snapshot.text.as_str(),
+                Field::Speaker => snapshot.speaker.as_deref().unwrap_or_default(),
+                Field::Segment => snapshot.segment.as_deref().unwrap_or_default(),
+            };
+
+            if file.last_value.as_deref() == Some(value) {
+                continue;
+            }
+
+            // Em caso de falha, o valor é gravado novamente no próximo quadro
+            if write_atomic(&file.path, value).is_ok() {
+                file.last_value = Some(value.to_string());
+            }
+        }
+    }
+}
+
+/// Grava em um arquivo temporário e renomeia, para o leitor nunca ver o arquivo pela metade
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_loader::RegressivaConfig;
+    use crate::timer::Countdown;
+    use std::time::Duration;
+
+    #[test]
+    fn files_follow_the_displayed_values() {
+        let dir = std::env::temp_dir().join(format!("regressiva-texto-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let tempo = dir.join("tempo.txt");
+        let orador = dir.join("orador.txt");
+
+        let config: ArquivosTextoConfig = toml::from_str(&format!(
+            "habilitado = true\narquivo_tempo = {:?}\narquivo_orador = {:?}\narquivo_segmento = \"\"",
+            tempo, orador
+        ))
+        .unwrap();
+        let mut output = TextOutput::new(&config);
+
+        let regressiva = RegressivaConfig::default();
+        let mut countdown = Countdown::new(Duration::new(90, 0));
+        output.publish(&countdown.snapshot(&regressiva));
+        assert_eq!(fs::read_to_string(&tempo).unwrap(), "01:30");
+        assert_eq!(fs::read_to_string(&orador).unwrap(), "");
+
+        countdown.duration = Duration::from_millis(89_500);
+        countdown.speaker = Some("Ana".to_string());
+        output.publish(&countdown.snapshot(&regressiva));
+        assert_eq!(fs::read_to_string(&tempo).unwrap(), "01:29");
+        assert_eq!(fs::read_to_string(&orador).unwrap(), "Ana");
+        assert!(!dir.join("tempo.txt.tmp").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
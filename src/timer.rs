@@ -0,0 +1,175 @@
+use crate::config_loader::RegressivaConfig;
+use sdl2::pixels::Color;
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Cor normal do timer
+pub const TIMER_COLOR: Color = Color::WHITE;
+
+/// Cor do timer nos segundos pares do alerta
+pub const ALERT_COLOR: Color = Color::RED;
+
+/// Situação atual da contagem regressiva
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerStatus {
+    Running,
+    Paused,
+    Finished,
+}
+
+impl TimerStatus {
+    /// Retorna o nome usado nas integrações externas
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimerStatus::Running => "running",
+            TimerStatus::Paused => "paused",
+            TimerStatus::Finished => "finished",
+        }
+    }
+}
+
+/// Estágio de alerta do timer, usado para colorir e piscar o display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStage {
+    Normal,
+    Warning,
+    Zero,
+}
+
+impl AlertStage {
+    /// Retorna o nome usado nas integrações externas
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertStage::Normal => "normal",
+            AlertStage::Warning => "warning",
+            AlertStage::Zero => "zero",
+        }
+    }
+}
+
+/// Fotografia do estado do timer, enviada para as integrações externas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimerSnapshot {
+    pub remaining_ms: u64,
+    pub text: String,
+    pub status: TimerStatus,
+    pub alert_stage: AlertStage,
+    pub speaker: Option<String>,
+    pub segment: Option<String>,
+}
+
+impl TimerSnapshot {
+    /// Compara dois estados ignorando os milissegundos restantes
+    pub fn same_state(&self, other: &TimerSnapshot) -> bool {
+        self.text == other.text
+            && self.status == other.status
+            && self.alert_stage == other.alert_stage
+            && self.speaker == other.speaker
+            && self.segment == other.segment
+    }
+
+    /// Retorna a cor do timer, piscando durante o alerta
+    pub fn color(&self) -> Color {
+        if self.alert_stage == AlertStage::Warning && (self.remaining_ms / 1000).is_multiple_of(2) {
+            ALERT_COLOR
+        } else {
+            TIMER_COLOR
+        }
+    }
+}
+
+/// Estado da contagem regressiva
+pub struct Countdown {
+    pub start_time: Instant,
+    pub duration: Duration,
+    pub is_running: bool,
+    pub speaker: Option<String>,
+    pub segment: Option<String>,
+    pub segment_index: Option<usize>,
+    /// Quantidade de resets feitos, para as integrações detectarem o evento
+    pub resets: u64,
+}
+
+impl Countdown {
+    pub fn new(duration: Duration) -> Self {
+        Countdown {
+            start_time: Instant::now(),
+            duration,
+            is_running: false,
+            speaker: None,
+            segment: None,
+            segment_index: None,
+            resets: 0,
+        }
+    }
+
+    /// Desconta o tempo decorrido desde a última atualização
+    pub fn update(&mut self) {
+        if self.is_running {
+            let elapsed = self.start_time.elapsed();
+            if self.duration > elapsed {
+                self.duration -= elapsed;
+            } else {
+                self.duration = Duration::new(0, 0);
+                self.is_running = false;
+            }
+        }
+        self.start_time = Instant::now();
+    }
+
+    /// Retorna os segundos restantes
+    pub fn remaining_secs(&self) -> u64 {
+        self.duration.as_secs()
+    }
+
+    /// Retorna quanto falta para o texto exibido mudar, se a contagem estiver correndo
+    pub fn until_next_second(&self) -> Option<Duration> {
+        // A margem garante que o segundo já virou quando o loop acordar
+        self.is_running.then(|| {
+            Duration::from_nanos(u64::from(self.duration.subsec_nanos())) + Duration::from_millis(1)
+        })
+    }
+
+    /// Retorna a situação atual do timer
+    pub fn status(&self) -> TimerStatus {
+        if self.is_running {
+            TimerStatus::Running
+        } else if self.duration.is_zero() {
+            TimerStatus::Finished
+        } else {
+            TimerStatus::Paused
+        }
+    }
+
+    /// Retorna o estágio de alerta conforme o tempo de alerta configurado
+    pub fn alert_stage(&self, config: &RegressivaConfig) -> AlertStage {
+        let remaining_secs = self.remaining_secs();
+        if remaining_secs == 0 {
+            AlertStage::Zero
+        } else if remaining_secs <= config.tempo.get_tempo_alerta_regressiva() {
+            AlertStage::Warning
+        } else {
+            AlertStage::Normal
+        }
+    }
+
+    /// Gera a fotografia do estado atual
+    pub fn snapshot(&self, config: &RegressivaConfig) -> TimerSnapshot {
+        TimerSnapshot {
+            remaining_ms: self.duration.as_millis() as u64,
+            text: format_timer(self.duration),
+            status: self.status(),
+            alert_stage: self.alert_stage(config),
+            speaker: self.speaker.clone(),
+            segment: self.segment.clone(),
+        }
+    }
+}
+
+/// Formata a duração no padrão MM:SS exibido nas janelas
+pub fn format_timer(duration: Duration) -> String {
+    let remaining_secs = duration.as_secs();
+    format!("{:02}:{:02}", remaining_secs / 60, remaining_secs % 60)
+}
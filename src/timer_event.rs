@@ -0,0 +1,37 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Eventos do timer que disparam integrações
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerEvent {
+    Start,
+    Pause,
+    Reset,
+    Threshold,
+    Zero,
+    Segment,
+}
+
+impl TimerEvent {
+    /// Todos os eventos, na ordem em que aparecem na documentação
+    pub const ALL: [TimerEvent; 6] = [
+        TimerEvent::Start,
+        TimerEvent::Pause,
+        TimerEvent::Reset,
+        TimerEvent::Threshold,
+        TimerEvent::Zero,
+        TimerEvent::Segment,
+    ];
+
+    /// Retorna o nome do evento usado no Config.toml
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimerEvent::Start => "start",
+            TimerEvent::Pause => "pause",
+            TimerEvent::Reset => "reset",
+            TimerEvent::Threshold => "threshold",
+            TimerEvent::Zero => "zero",
+            TimerEvent::Segment => "segment",
+        }
+    }
+}
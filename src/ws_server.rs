@@ -0,0 +1,284 @@
+use crate::commands::{self, Command, RemoteRequest};
+use crate::config_loader::WebsocketConfig;
+use crate::timer::TimerSnapshot;
+use serde_json::json;
+use std::io::ErrorKind;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::Message;
+
+/// Trata uma mensagem de texto recebida e retorna a resposta ao cliente, se houver
+pub type MessageHandler = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Distribui mensagens para todos os clientes conectados
+#[derive(Clone, Default)]
+pub struct Broadcaster {
+    clients: Arc<Mutex<Vec<Sender<String>>>>,
+    last_message: Arc<Mutex<Option<String>>>,
+}
+
+impl Broadcaster {
+    /// Registra um novo cliente, que recebe imediatamente a última mensagem enviada
+    pub fn subscribe(&self) -> Receiver<String> {
+        let (tx, rx) = mpsc::channel();
+        if let Some(message) = self.last_message.lock().unwrap().as_ref() {
+            let _ = tx.send(message.clone());
+        }
+        self.clients.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Envia a mensagem para todos os clientes, descartando os desconectados
+    pub fn publish(&self, message: &str) {
+        *self.last_message.lock().unwrap() = Some(message.to_string());
+        self.clients
+            .lock()
+            .unwrap()
+            .retain(|client| client.send(message.to_string()).is_ok());
+    }
+}
+
+/// Publica o estado do timer a cada mudança e no intervalo configurado
+pub struct StatePublisher {
+    broadcaster: Broadcaster,
+    last_snapshot: Option<TimerSnapshot>,
+    last_sent: Instant,
+    interval: Duration,
+}
+
+impl StatePublisher {
+    pub fn new(broadcaster: Broadcaster, interval: Duration) -> Self {
+        StatePublisher {
+            broadcaster,
+            last_snapshot: None,
+            last_sent: Instant::now(),
+            interval,
+        }
+    }
+
+    /// Envia o estado se ele mudou ou se o intervalo de envio foi atingido
+    pub fn publish(&mut self, snapshot: &TimerSnapshot) {
+        let changed = match &self.last_snapshot {
+            Some(last) => !last.same_state(snapshot),
+            None => true,
+        };
+
+        if !changed && self.last_sent.elapsed() < self.interval {
+            return;
+        }
+
+        if let Ok(message) = serde_json::to_string(snapshot) {
+            self.broadcaster.publish(&message);
+        }
+        self.last_snapshot = Some(snapshot.clone());
+        self.last_sent = Instant::now();
+    }
+
+    /// Retorna quando o próximo envio periódico é devido
+    pub fn next_publish(&self) -> Instant {
+        self.last_sent + self.interval
+    }
+}
+
+/// Inicia o servidor WebSocket em uma thread separada
+pub fn start(config: &WebsocketConfig, commands: Sender<RemoteRequest>) -> std::io::Result<Broadcaster> {
+    let listener = TcpListener::bind(config.get_endereco())?;
+    let commands = config.permitir_comandos().then_some(commands);
+    Ok(serve(listener, commands))
+}
+
+/// Atende os clientes do listener; sem canal de comandos o servidor é somente leitura
+pub fn serve(listener: TcpListener, commands: Option<Sender<RemoteRequest>>) -> Broadcaster {
+    let handler: MessageHandler =
+        Arc::new(move |text| Some(handle_command(text, commands.as_ref())));
+    serve_with(listener, handler)
+}
+
+/// Atende os clientes do listener, respondendo as mensagens recebidas com o handler
+pub fn serve_with(listener: TcpListener, handler: MessageHandler) -> Broadcaster {
+    let broadcaster = Broadcaster::default();
+
+    let accept_broadcaster = broadcaster.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let updates = accept_broadcaster.subscribe();
+            let handler = handler.clone();
+            thread::spawn(move || handle_client(stream, updates, handler));
+        }
+    });
+
+    broadcaster
+}
+
+// Troca mensagens com um cliente até ele desconectar
+fn handle_client(stream: TcpStream, updates: Receiver<String>, handler: MessageHandler) {
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(_) => return,
+    };
+
+    // A leitura com timeout permite alternar entre comandos recebidos e atualizações
+    if socket
+        .get_ref()
+        .set_read_timeout(Some(Duration::from_millis(20)))
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if let Some(reply) = handler(&text) {
+                    if socket.send(Message::Text(reply)).is_err() {
+                        break;
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+
+        loop {
+            match updates.try_recv() {
+                Ok(message) => {
+                    if socket.send(Message::Text(message)).is_err() {
+                        return;
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            }
+        }
+    }
+}
+
+// Interpreta um comando JSON e retorna a resposta para o cliente
+fn handle_command(text: &str, commands: Option<&Sender<RemoteRequest>>) -> String {
+    let Some(commands) = commands else {
+        return error_reply("comandos desabilitados neste servidor");
+    };
+
+    let command: Command = match serde_json::from_str(text) {
+        Ok(command) => command,
+        Err(e) => return error_reply(&format!("comando inválido: {}", e)),
+    };
+
+    let name = command.name();
+    match commands::send_command(commands, command) {
+        Ok(()) => json!({ "type": "ack", "command": name }).to_string(),
+        Err(e) => error_reply(&e),
+    }
+}
+
+fn error_reply(message: &str) -> String {
+    json!({ "type": "error", "error": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_loader::RegressivaConfig;
+    use crate::timer::Countdown;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    // Simula o loop principal aplicando os comandos recebidos
+    fn spawn_engine(countdown: Arc<Mutex<Countdown>>) -> Sender<RemoteRequest> {
+        let (tx, rx) = mpsc::channel::<RemoteRequest>();
+        thread::spawn(move || {
+            let config = RegressivaConfig::default();
+            for request in rx {
+                let result = request.command.apply(&mut countdown.lock().unwrap(), &config);
+                let _ = request.reply.send(result);
+            }
+        });
+        tx
+    }
+
+    // Lê mensagens até encontrar uma resposta a comando, ignorando o estado transmitido
+    fn read_reply(
+        socket: &mut tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<TcpStream>>,
+    ) -> Value {
+        loop {
+            if let Message::Text(text) = socket.read().unwrap() {
+                let value: Value = serde_json::from_str(&text).unwrap();
+                if value.get("type").is_some() {
+                    return value;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn remote_commands_are_applied_and_acknowledged() {
+        let countdown = Arc::new(Mutex::new(Countdown::new(Duration::new(30, 0))));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let broadcaster = serve(listener, Some(spawn_engine(countdown.clone())));
+        broadcaster.publish(r#"{"text":"00:30"}"#);
+
+        let (mut socket, _) = tungstenite::connect(format!("ws://{}", addr)).unwrap();
+
+        socket
+            .send(Message::Text(r#"{"command":"set","seconds":90}"#.to_string()))
+            .unwrap();
+        let reply = read_reply(&mut socket);
+        assert_eq!(reply["type"], "ack");
+        assert_eq!(reply["command"], "set");
+        assert_eq!(countdown.lock().unwrap().duration, Duration::new(90, 0));
+
+        socket
+            .send(Message::Text(r#"{"command":"nudge","seconds":-100}"#.to_string()))
+            .unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "ack");
+        assert!(countdown.lock().unwrap().duration.is_zero());
+
+        socket
+            .send(Message::Text(r#"{"command":"preset","index":3}"#.to_string()))
+            .unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "ack");
+        assert_eq!(countdown.lock().unwrap().duration, Duration::new(30, 0));
+
+        socket
+            .send(Message::Text(r#"{"command":"speaker","name":"Ana"}"#.to_string()))
+            .unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "ack");
+        assert_eq!(countdown.lock().unwrap().speaker.as_deref(), Some("Ana"));
+
+        socket.send(Message::Text(r#"{"command":"start"}"#.to_string())).unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "ack");
+        assert!(countdown.lock().unwrap().is_running);
+
+        socket
+            .send(Message::Text(r#"{"command":"preset","index":42}"#.to_string()))
+            .unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "error");
+
+        socket
+            .send(Message::Text(r#"{"command":"next_segment"}"#.to_string()))
+            .unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "error");
+
+        socket.send(Message::Text("não é json".to_string())).unwrap();
+        assert_eq!(read_reply(&mut socket)["type"], "error");
+    }
+
+    #[test]
+    fn read_only_server_rejects_commands() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let _broadcaster = serve(listener, None);
+
+        let (mut socket, _) = tungstenite::connect(format!("ws://{}", addr)).unwrap();
+        socket.send(Message::Text(r#"{"command":"start"}"#.to_string())).unwrap();
+        let reply = read_reply(&mut socket);
+        assert_eq!(reply["type"], "error");
+    }
+}
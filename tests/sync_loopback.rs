@@ -0,0 +1,185 @@
+//! Primário e réplica como dois processos do programa, ligados pelo loopback
+//!
+//! Cada instância abre as janelas com o driver de vídeo "dummy" do SDL e expõe o estado pela
+//! API HTTP, usada aqui para comandar o primário e conferir o que a réplica exibe.
+
+use serde_json::Value;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Tempo máximo para uma instância abrir ou a réplica alcançar o primário
+const CONVERGE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Diferença aceita entre o tempo restante do primário e o da réplica
+const TOLERANCE_MS: i64 = 500;
+
+// Processo do programa, encerrado ao sair do teste mesmo em caso de falha
+struct Instance {
+    child: Child,
+    http: String,
+}
+
+impl Instance {
+    fn start(dir: &Path, config: &Path, http: String) -> Instance {
+        let child = Command::new(env!("CARGO_BIN_EXE_regressiva"))
+            .arg("--config")
+            .arg(config)
+            .current_dir(dir)
+            .spawn()
+            .unwrap();
+        let instance = Instance { child, http };
+        wait_until(|| instance.status().is_some());
+        instance
+    }
+
+    fn status(&self) -> Option<Value> {
+        let (code, body) = http_request(&self.http, "GET", "/status")?;
+        if code != 200 {
+            return None;
+        }
+        serde_json::from_str(&body).ok()
+    }
+
+    fn command(&self, path: &str) {
+        let (code, body) = http_request(&self.http, "POST", path).unwrap();
+        assert_eq!(code, 200, "{} {}", path, body);
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// Porta livre no loopback
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn http_request(addr: &str, method: &str, path: &str) -> Option<(u16, String)> {
+    let mut stream = TcpStream::connect(addr).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(2))).ok()?;
+    write!(stream, "{} {} HTTP/1.1\r\n\r\n", method, path).ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+    let code = response.get(9..12)?.parse().ok()?;
+    let (_, body) = response.split_once("\r\n\r\n")?;
+    Some((code, body.to_string()))
+}
+
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let started = Instant::now();
+    while !condition() {
+        assert!(
+            started.elapsed() < CONVERGE_TIMEOUT,
+            "tempo esgotado esperando as instâncias"
+        );
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+// Espera a réplica mostrar o mesmo estado e o mesmo tempo restante do primário
+fn wait_for_convergence(primary: &Instance, replica: &Instance) {
+    wait_until(|| {
+        let (Some(primary), Some(replica)) = (primary.status(), replica.status()) else {
+            return false;
+        };
+        let remaining = |state: &Value| state["remaining_ms"].as_i64().unwrap_or(-1);
+        primary["status"] == replica["status"]
+            && (remaining(&primary) - remaining(&replica)).abs() <= TOLERANCE_MS
+    });
+}
+
+fn write_config(dir: &Path, name: &str, http_port: u16, sincronia: &str) -> PathBuf {
+    let path = dir.join(name);
+    let config = format!(
+        r#"
+[boas_vindas]
+mostrar_boas_vindas = false
+
+[debug]
+arquivo = ""
+
+[renderizacao]
+janela_timer = "software"
+janela_controle = "software"
+driver_video = "dummy"
+
+[http]
+habilitado = true
+porta = {}
+
+[sincronia]
+{}
+"#,
+        http_port, sincronia
+    );
+    fs::write(&path, config).unwrap();
+    path
+}
+
+#[test]
+fn replica_process_follows_the_primary_across_restarts() {
+    let dir = std::env::temp_dir().join(format!("regressiva-sincronia-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let sync_addr = format!("127.0.0.1:{}", free_port());
+    let primary_http = free_port();
+    let replica_http = free_port();
+    let primary_config = write_config(
+        &dir,
+        "primario.toml",
+        primary_http,
+        &format!(
+            "modo = \"primario\"\nescuta = \"{}\"\nintervalo_ms = 100",
+            sync_addr
+        ),
+    );
+    let replica_config = write_config(
+        &dir,
+        "replica.toml",
+        replica_http,
+        &format!(
+            "modo = \"replica\"\nprimario = \"{}\"\npromover_automaticamente = false",
+            sync_addr
+        ),
+    );
+    let primary_http = format!("127.0.0.1:{}", primary_http);
+    let replica_http = format!("127.0.0.1:{}", replica_http);
+
+    let primary = Instance::start(&dir, &primary_config, primary_http.clone());
+    primary.command("/set?seconds=300");
+    primary.command("/start");
+
+    let replica = Instance::start(&dir, &replica_config, replica_http.clone());
+    wait_for_convergence(&primary, &replica);
+    assert_eq!(replica.status().unwrap()["status"], "running");
+
+    // Uma réplica reiniciada volta a seguir o primário sem reiniciar a contagem
+    drop(replica);
+    let replica = Instance::start(&dir, &replica_config, replica_http);
+    wait_for_convergence(&primary, &replica);
+
+    // Com o primário reiniciado, a réplica reconecta e passa a mostrar o novo estado
+    drop(primary);
+    let primary = Instance::start(&dir, &primary_config, primary_http);
+    primary.command("/set?seconds=42");
+    wait_for_convergence(&primary, &replica);
+    assert_eq!(replica.status().unwrap()["status"], "paused");
+    assert_eq!(replica.status().unwrap()["text"], "00:42");
+
+    drop(replica);
+    drop(primary);
+    fs::remove_dir_all(&dir).unwrap();
+}